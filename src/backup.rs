@@ -0,0 +1,187 @@
+use crate::enums::SaveError;
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The extension of the file next to each backup that says where it is from
+const SOURCE_EXTENSION: &str = "source";
+
+/// A backup of a save file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    /// Where the backup is
+    pub path: PathBuf,
+    /// The file the backup was taken of
+    pub original: PathBuf,
+    /// When the backup was taken, in milliseconds since the unix epoch
+    pub created: u64,
+}
+
+impl Backup {
+    /// Returns when the backup was taken, like 2025-04-01 13:45:10 UTC
+    pub fn created_label(&self) -> String {
+        let (date, time) = split_timestamp(self.created / 1000);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            date.0, date.1, date.2, time.0, time.1, time.2
+        )
+    }
+
+    /// Reads the data of the backup
+    pub fn read(&self) -> Result<Vec<u8>, SaveError> {
+        Ok(std::fs::read(&self.path)?)
+    }
+}
+
+/// Writes a file, first backing up the file it replaces
+/// Returns the backup, if there was a file to back up
+pub fn write_with_backup(
+    path: &Path,
+    data: &[u8],
+    backup_dir: &Path,
+    keep: usize,
+) -> Result<Option<Backup>, SaveError> {
+    let backup = backup_file(path, backup_dir, keep)?;
+    std::fs::write(path, data)?;
+    Ok(backup)
+}
+
+/// Copies a file into the backup directory, with the time in its name
+///
+/// Only the newest `keep` backups of the same file are kept, older ones are
+/// deleted. Nothing is backed up if the file does not exist or keep is 0.
+pub fn backup_file(
+    path: &Path,
+    backup_dir: &Path,
+    keep: usize,
+) -> Result<Option<Backup>, SaveError> {
+    if keep == 0 || !path.is_file() {
+        return Ok(None);
+    }
+    std::fs::create_dir_all(backup_dir)?;
+
+    let original = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    // Backups are ordered by when they were taken, so two taken in the same
+    // millisecond are moved apart to keep the newest one last
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let created = list_backups(backup_dir)
+        .iter()
+        .find(|backup| backup.original == original)
+        .map_or(now, |newest| now.max(newest.created + 1));
+    let (date, time) = split_timestamp(created / 1000);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let name = format!(
+        "{}-{:04}{:02}{:02}-{:02}{:02}{:02}",
+        stem, date.0, date.1, date.2, time.0, time.1, time.2
+    );
+
+    // Two backups in the same second get a number on the end
+    let mut backup_path = backup_dir.join(format!("{}{}", name, extension));
+    let mut n = 1;
+    while backup_path.exists() {
+        backup_path = backup_dir.join(format!("{}-{}{}", name, n, extension));
+        n += 1;
+    }
+
+    std::fs::copy(path, &backup_path)?;
+    std::fs::write(
+        source_path(&backup_path),
+        format!("{}\n{}\n", original.display(), created),
+    )?;
+
+    prune(backup_dir, &original, keep)?;
+    Ok(Some(Backup { path: backup_path, original, created }))
+}
+
+/// Lists the backups in the backup directory, newest first
+pub fn list_backups(backup_dir: &Path) -> Vec<Backup> {
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == SOURCE_EXTENSION)
+        })
+        .filter_map(|source| read_source(&source))
+        .filter(|backup| backup.path.is_file())
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    backups
+}
+
+/// Puts a backup back in place of the file it was taken of
+/// The file being replaced is backed up first, so a restore can be undone
+pub fn restore(
+    backup: &Backup,
+    backup_dir: &Path,
+    keep: usize,
+) -> Result<(), SaveError> {
+    let data = backup.read()?;
+    write_with_backup(&backup.original, &data, backup_dir, keep)?;
+    Ok(())
+}
+
+/// Deletes all but the newest `keep` backups of a file
+fn prune(
+    backup_dir: &Path,
+    original: &Path,
+    keep: usize,
+) -> Result<(), SaveError> {
+    let old = list_backups(backup_dir)
+        .into_iter()
+        .filter(|backup| backup.original == original)
+        .skip(keep);
+    for backup in old {
+        std::fs::remove_file(&backup.path)?;
+        std::fs::remove_file(source_path(&backup.path))?;
+    }
+    Ok(())
+}
+
+/// Returns the path of the file that says where a backup is from
+fn source_path(backup_path: &Path) -> PathBuf {
+    let mut name = backup_path.as_os_str().to_owned();
+    name.push(".");
+    name.push(SOURCE_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Reads the file that says where a backup is from
+fn read_source(source: &Path) -> Option<Backup> {
+    let text = std::fs::read_to_string(source).ok()?;
+    let mut lines = text.lines();
+    let original = PathBuf::from(lines.next()?);
+    let created = lines.next()?.trim().parse().ok()?;
+    Some(Backup { path: source.with_extension(""), original, created })
+}
+
+/// Splits seconds since the unix epoch into a UTC date and time
+/// The date is worked out with the civil from days algorithm, see
+/// http://howardhinnant.github.io/date_algorithms.html
+fn split_timestamp(secs: u64) -> ((i64, u32, u32), (u32, u32, u32)) {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let time =
+        ((rem / 3600) as u32, (rem % 3600 / 60) as u32, (rem % 60) as u32);
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    ((year, month, day), time)
+}
@@ -0,0 +1,372 @@
+use crate::backup;
+use crate::config::Config;
+use crate::diff::Diff;
+use crate::enums::{
+    ExportFormat, MergeStrategy, MetaFlag, SaveError, SaveKind, SaveType,
+};
+use crate::lua::{self, SaveValue};
+use crate::registry::{Registries, init_registries};
+use crate::saves::Meta;
+
+use clap::{Args, Parser, Subcommand};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Edit Balatro save files from the command line
+/// Run without any arguments to open the editor instead
+#[derive(Parser, Debug)]
+#[command(name = "bal-save", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+    /// Read the items files from this directory, instead of the items
+    /// directory in the config directory
+    #[arg(long, global = true)]
+    pub items_dir: Option<PathBuf>,
+}
+
+/// The commands the command line interface supports
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print a summary of a save file
+    Info {
+        /// The save file to read
+        file: PathBuf,
+    },
+    /// Unlock all items in a meta file, or only the items of one type
+    Unlock {
+        /// The meta file to edit
+        file: PathBuf,
+        /// Only unlock items whose key starts with this, like j_
+        #[arg(long = "type")]
+        type_: Option<String>,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Lock all items in a meta file, or only the items of one type
+    Lock {
+        /// The meta file to edit
+        file: PathBuf,
+        /// Only lock items whose key starts with this, like j_
+        #[arg(long = "type")]
+        type_: Option<String>,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Replace a meta file with the one a new profile starts with
+    ResetDefaults {
+        /// The meta file to replace
+        file: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Set the flags of a single item in a meta file
+    Set {
+        /// The meta file to edit
+        file: PathBuf,
+        /// The key of the item, like j_blueprint
+        key: String,
+        /// Whether the item is alerted
+        #[arg(long)]
+        alerted: Option<bool>,
+        /// Whether the item is discovered
+        #[arg(long)]
+        discovered: Option<bool>,
+        /// Whether the item is unlocked
+        #[arg(long)]
+        unlocked: Option<bool>,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Merge another meta file into a meta file, printing what changed
+    Merge {
+        /// The meta file to merge into
+        file: PathBuf,
+        /// The meta file to merge from
+        other: PathBuf,
+        /// How the flags of the two files are combined
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Union)]
+        strategy: MergeStrategy,
+        /// Only print what would change, without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Print what differs between two save files
+    /// Two meta files are compared per item
+    Diff {
+        /// The file to compare from
+        old: PathBuf,
+        /// The file to compare to
+        new: PathBuf,
+        /// Compare meta files key by key instead of per item
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Export the items of a meta file as JSON, TOML or CSV
+    ExportMeta {
+        /// The meta file to read
+        file: PathBuf,
+        /// The format to write, worked out from the output file if not given
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+        /// Write to this file instead of printing
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Replace a meta file with the items of a JSON, TOML or CSV export
+    ImportMeta {
+        /// The exported file to read
+        input: PathBuf,
+        /// The meta file to write
+        file: PathBuf,
+        /// The format to read, worked out from the input file if not given
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+        /// Allow items that are not known, like modded ones
+        #[arg(long)]
+        allow_unknown: bool,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Print the decompressed contents of a save file
+    Export {
+        /// The save file to read
+        file: PathBuf,
+        /// Write to this file instead of printing
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Where an edited file is written
+#[derive(Args, Debug)]
+pub struct Output {
+    /// Write to this file instead of over the input file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Keep the backup of the replaced file in this directory, instead of
+    /// the one in the settings
+    #[arg(long)]
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// An error from running a command
+#[derive(Debug)]
+pub enum CliError {
+    /// The file could not be loaded or saved
+    Save(SaveError),
+    /// The item is not in the meta file
+    UnknownItem(String),
+    /// Set was run without any flags to set
+    NoFlags,
+    /// The item can not have the flag, like an alerted voucher
+    CanNotHave(String, MetaFlag),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Save(err) => write!(f, "{}", err),
+            CliError::UnknownItem(key) => {
+                write!(f, "'{}' is not an item in the meta file", key)
+            }
+            CliError::NoFlags => write!(
+                f,
+                "Nothing to set, pass --alerted, --discovered or --unlocked"
+            ),
+            CliError::CanNotHave(key, flag) => {
+                write!(f, "'{}' can not be {}", key, flag)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<SaveError> for CliError {
+    fn from(err: SaveError) -> Self {
+        CliError::Save(err)
+    }
+}
+
+/// Parses the command line arguments and runs the command
+/// Returns the exit code for the process, which is non-zero on errors
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(dir) = cli.items_dir {
+        init_registries(Registries::load_from(&dir));
+    }
+    match execute(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs a command
+pub fn execute(command: Command) -> Result<(), CliError> {
+    match command {
+        Command::Info { file } => {
+            let mut save = SaveType::from_file(&file)?;
+            save.find_version(&file);
+            print!("{}", save.summary());
+        }
+        Command::Unlock { file, type_, output } => {
+            let mut meta = read_meta(&file)?;
+            meta.unlock_all_type(type_.as_deref().unwrap_or(""));
+            write_meta(&meta, &file, output)?;
+        }
+        Command::Lock { file, type_, output } => {
+            let mut meta = read_meta(&file)?;
+            meta.lock_all_type(type_.as_deref().unwrap_or(""));
+            write_meta(&meta, &file, output)?;
+        }
+        Command::ResetDefaults { file, output } => {
+            // The file is still read, so a file that is not a meta file is
+            // never replaced by accident
+            read_meta(&file)?;
+            write_meta(&Meta::from_defaults(), &file, output)?;
+        }
+        Command::Set { file, key, alerted, discovered, unlocked, output } => {
+            let flags: Vec<(MetaFlag, bool)> = [
+                (MetaFlag::Alerted, alerted),
+                (MetaFlag::Discovered, discovered),
+                (MetaFlag::Unlocked, unlocked),
+            ]
+            .into_iter()
+            .filter_map(|(flag, value)| value.map(|value| (flag, value)))
+            .collect();
+            if flags.is_empty() {
+                return Err(CliError::NoFlags);
+            }
+            let mut meta = read_meta(&file)?;
+            let item = meta
+                .get_item(&key)
+                .ok_or_else(|| CliError::UnknownItem(key.clone()))?;
+            // Like the editor, flags the game never gives the item are not
+            // set, but here that fails the command
+            if let Some(&(flag, _)) =
+                flags.iter().find(|(flag, _)| !item.can_have(*flag))
+            {
+                return Err(CliError::CanNotHave(key, flag));
+            }
+            meta.record(|meta| {
+                if let Some(item) = meta.get_item(&key) {
+                    for (flag, value) in flags {
+                        item.set_flag(flag, value);
+                    }
+                }
+            });
+            write_meta(&meta, &file, output)?;
+        }
+        Command::Merge { file, other, strategy, dry_run, output } => {
+            let mut meta = read_meta(&file)?;
+            let changes = meta.merge(&read_meta(&other)?, strategy);
+            if changes.is_empty() {
+                println!("Nothing to merge");
+            }
+            for change in changes.iter() {
+                println!("{}", change);
+            }
+            if !dry_run && !changes.is_empty() {
+                write_meta(&meta, &file, output)?;
+            }
+        }
+        Command::Diff { old, new, raw } => {
+            let old = SaveValue::from_lua_data(&read_file(&old)?)?;
+            let new = SaveValue::from_lua_data(&read_file(&new)?)?;
+            let diff = Diff::new(&old, &new, raw)?;
+            if diff.is_empty() {
+                println!("The files are the same");
+            }
+            for line in diff.lines() {
+                println!("{}", line);
+            }
+        }
+        Command::ExportMeta { file, format, output } => {
+            let format = format
+                .or_else(|| output.as_deref().and_then(ExportFormat::from_path))
+                .unwrap_or_default();
+            let text = read_meta(&file)?.export(format)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(path, text).map_err(SaveError::Io)?
+                }
+                None => print!("{}", text),
+            }
+        }
+        Command::ImportMeta { input, file, format, allow_unknown, output } => {
+            // Like ResetDefaults, a file that is not a meta file is never
+            // replaced by accident
+            let mut meta = read_meta(&file)?;
+            let format = format
+                .or_else(|| ExportFormat::from_path(&input))
+                .unwrap_or_default();
+            let text =
+                std::fs::read_to_string(&input).map_err(SaveError::Io)?;
+            // The import is applied over the target like in the app, so the
+            // target keeps its VERSION
+            let version = meta.version().map(str::to_string);
+            let imported =
+                Meta::import_for(&text, format, allow_unknown, version)?;
+            let changes = meta.diff(&imported);
+            meta.apply(&changes);
+            write_meta(&meta, &file, output)?;
+        }
+        Command::Export { file, output } => {
+            let text = lua::inflate(&read_file(&file)?)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(path, text).map_err(SaveError::Io)?
+                }
+                None => println!("{}", text),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads a file, turning a failure into a SaveError
+fn read_file(path: &Path) -> Result<Vec<u8>, SaveError> {
+    std::fs::read(path).map_err(SaveError::Io)
+}
+
+/// Reads a meta file, failing if the file is some other kind of save
+/// A meta without a version takes the one of the run saved next to it, see
+/// Meta::find_version
+fn read_meta(path: &Path) -> Result<Meta, SaveError> {
+    match SaveType::from_file(path)? {
+        SaveType::Meta(mut meta) => {
+            meta.find_version(path);
+            Ok(meta)
+        }
+        save => Err(SaveError::WrongFileType {
+            expected: SaveKind::Meta,
+            found: save.kind(),
+        }),
+    }
+}
+
+/// Writes a meta file over the input file, or to the output if one is given
+/// A file that is replaced is backed up first
+fn write_meta(
+    meta: &Meta,
+    input: &Path,
+    output: Output,
+) -> Result<(), SaveError> {
+    let path = output.output.as_deref().unwrap_or(input);
+    let config = Config::load();
+    let backup_dir = output.backup_dir.unwrap_or_else(|| config.backup_dir());
+    backup::write_with_backup(
+        path,
+        &meta.to_lua_data()?,
+        &backup_dir,
+        config.backups_to_keep,
+    )?;
+    Ok(())
+}
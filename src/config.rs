@@ -0,0 +1,68 @@
+use crate::enums::SaveError;
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The name of the directory the app keeps its files in
+const APP_DIR: &str = "bal-save";
+
+/// The settings of the app itself, kept in config.toml in the config
+/// directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// How many backups of each file are kept, 0 turns backups off
+    pub backups_to_keep: usize,
+    /// Where backups are kept, instead of the default in the data directory
+    pub backup_dir: Option<PathBuf>,
+    /// Whether the open file is reloaded when the game writes it
+    pub watch_files: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { backups_to_keep: 10, backup_dir: None, watch_files: true }
+    }
+}
+
+impl Config {
+    /// Loads the config, using the defaults if there is no config file
+    /// A config file that can not be read also gives the defaults, so a bad
+    /// edit to it never stops the app from starting
+    pub fn load() -> Self {
+        config_dir()
+            .map(|dir| dir.join("config.toml"))
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the config to the config directory
+    pub fn save(&self) -> Result<(), SaveError> {
+        let dir = config_dir().ok_or_else(|| {
+            SaveError::Serialize("No config directory found".to_string())
+        })?;
+        let text = toml::to_string_pretty(self)
+            .map_err(|err| SaveError::Serialize(err.to_string()))?;
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("config.toml"), text)?;
+        Ok(())
+    }
+
+    /// Returns the directory backups are kept in
+    pub fn backup_dir(&self) -> PathBuf {
+        match &self.backup_dir {
+            Some(dir) => dir.clone(),
+            None => dirs::data_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join(APP_DIR)
+                .join("backups"),
+        }
+    }
+}
+
+/// Returns the directory the app keeps its config in, like
+/// ~/.config/bal-save on Linux
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
@@ -0,0 +1,176 @@
+use crate::enums::{SaveError, SaveKind};
+use crate::lua::{SaveKey, SaveValue, format_number, format_path};
+use crate::saves::{ItemChange, Meta};
+
+use std::fmt;
+
+/// How a value differs between two save files
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The value is only in the new file
+    Added(SaveValue),
+    /// The value is only in the old file
+    Removed(SaveValue),
+    /// The value is in both files, but is not the same
+    Changed(SaveValue, SaveValue),
+}
+
+/// A value that differs between two save files, and where it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    /// The keys that lead to the value, see format_path
+    pub path: Vec<SaveKey>,
+    /// How the value differs
+    pub change: Change,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = format_path(&self.path);
+        match &self.change {
+            Change::Added(value) => {
+                write!(f, "+ {} = {}", path, format_value(value))
+            }
+            Change::Removed(value) => {
+                write!(f, "- {} = {}", path, format_value(value))
+            }
+            Change::Changed(old, new) => write!(
+                f,
+                "~ {}: {} → {}",
+                path,
+                format_value(old),
+                format_value(new)
+            ),
+        }
+    }
+}
+
+/// The differences between two save files
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diff {
+    /// Every value that differs, in the order of the files
+    Tree(Vec<Difference>),
+    /// Every meta item that differs, by name
+    Meta(Vec<ItemChange>),
+}
+
+impl Diff {
+    /// Compares two decoded save files
+    /// Two meta files are compared per item, unless raw is set
+    pub fn new(
+        old: &SaveValue,
+        new: &SaveValue,
+        raw: bool,
+    ) -> Result<Self, SaveError> {
+        let both_meta = SaveKind::detect(old) == SaveKind::Meta
+            && SaveKind::detect(new) == SaveKind::Meta;
+        if both_meta && !raw {
+            let old = Meta::from_save_value(old.clone())?;
+            let new = Meta::from_save_value(new.clone())?;
+            return Ok(Diff::Meta(old.diff(&new)));
+        }
+        Ok(Diff::Tree(diff_values(old, new)))
+    }
+
+    /// Returns whether the files are the same
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Diff::Tree(differences) => differences.is_empty(),
+            Diff::Meta(changes) => changes.is_empty(),
+        }
+    }
+
+    /// Returns a line for each difference, starting with +, - or ~ for
+    /// added, removed and changed
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Diff::Tree(differences) => {
+                differences.iter().map(|d| d.to_string()).collect()
+            }
+            Diff::Meta(changes) => changes
+                .iter()
+                .map(|change| {
+                    let sign = match (&change.before, &change.after) {
+                        (None, _) => '+',
+                        (_, None) => '-',
+                        _ => '~',
+                    };
+                    format!("{} {}", sign, change)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Compares two values, returning everything that differs
+///
+/// Tables are compared key by key, anything else is compared as a whole.
+/// Integers and floats are the same number if they are equal, since the
+/// game does not tell them apart.
+pub fn diff_values(old: &SaveValue, new: &SaveValue) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_into(&mut Vec::new(), old, new, &mut differences);
+    differences
+}
+
+/// Compares two values at a path, adding what differs to the list
+fn diff_into(
+    path: &mut Vec<SaveKey>,
+    old: &SaveValue,
+    new: &SaveValue,
+    differences: &mut Vec<Difference>,
+) {
+    match (old, new) {
+        (SaveValue::Table(old_table), SaveValue::Table(new_table)) => {
+            for (key, old_value) in old_table.iter() {
+                path.push(key.clone());
+                match new_table.get(key.clone()) {
+                    Some(new_value) => {
+                        diff_into(path, old_value, new_value, differences)
+                    }
+                    None => differences.push(Difference {
+                        path: path.clone(),
+                        change: Change::Removed(old_value.clone()),
+                    }),
+                }
+                path.pop();
+            }
+            for (key, new_value) in new_table.iter() {
+                if !old_table.contains_key(key.clone()) {
+                    let mut path = path.clone();
+                    path.push(key.clone());
+                    differences.push(Difference {
+                        path,
+                        change: Change::Added(new_value.clone()),
+                    });
+                }
+            }
+        }
+        _ if same_leaf(old, new) => {}
+        _ => differences.push(Difference {
+            path: path.clone(),
+            change: Change::Changed(old.clone(), new.clone()),
+        }),
+    }
+}
+
+/// Checks if two values that are not both tables are the same
+fn same_leaf(old: &SaveValue, new: &SaveValue) -> bool {
+    match (old.as_f64(), new.as_f64()) {
+        (Some(old), Some(new)) => old == new,
+        _ => old == new,
+    }
+}
+
+/// Formats a value for a difference, tables only by their size
+fn format_value(value: &SaveValue) -> String {
+    match value {
+        SaveValue::Table(table) => {
+            format!("table ({} keys)", table.len())
+        }
+        SaveValue::String(s) => format!("\"{}\"", s),
+        SaveValue::Integer(i) => i.to_string(),
+        SaveValue::Float(f) => format_number(*f),
+        SaveValue::Bool(b) => b.to_string(),
+    }
+}
@@ -1,6 +1,18 @@
+mod exportformat;
+mod itemcategory;
+mod mergestrategy;
+mod metaflag;
 mod popuptype;
+mod saveerror;
+mod savekind;
 mod savetype;
 mod tabstate;
+pub use exportformat::ExportFormat;
+pub use itemcategory::ItemCategory;
+pub use mergestrategy::MergeStrategy;
+pub use metaflag::MetaFlag;
 pub use popuptype::PopupType;
+pub use saveerror::SaveError;
+pub use savekind::SaveKind;
 pub use savetype::SaveType;
 pub use tabstate::TabState;
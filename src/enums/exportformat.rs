@@ -0,0 +1,48 @@
+use std::fmt;
+use std::path::Path;
+
+/// Enum for the text formats a meta file can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// A JSON object with a list of items
+    #[default]
+    Json,
+    /// A TOML file with an [[items]] table per item
+    Toml,
+    /// A CSV file with a row per item
+    Csv,
+}
+
+impl ExportFormat {
+    /// Every format, in the order the app lists them
+    pub const ALL: [ExportFormat; 3] =
+        [ExportFormat::Json, ExportFormat::Toml, ExportFormat::Csv];
+
+    /// Works out the format from the extension of a file
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    /// Returns the file extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Toml => "toml",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Toml => "TOML",
+            ExportFormat::Csv => "CSV",
+        };
+        write!(f, "{}", name)
+    }
+}
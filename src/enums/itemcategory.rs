@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Enum for the categories of items in a meta file
+/// The category of an item is worked out from the prefix of its key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemCategory {
+    /// Jokers, like j_blueprint
+    Joker,
+    /// Tarot, planet and spectral cards, like c_fool
+    Consumable,
+    /// Vouchers, like v_grabber
+    Voucher,
+    /// Decks, like b_red
+    Deck,
+    /// Editions, like e_foil
+    Edition,
+    /// Blinds, like bl_ox
+    Blind,
+    /// Tags, like tag_double
+    Tag,
+    /// Booster packs, like p_buffoon_mega_1
+    Booster,
+}
+
+impl ItemCategory {
+    /// Every category, in the order the app lists them
+    pub const ALL: [ItemCategory; 8] = [
+        ItemCategory::Joker,
+        ItemCategory::Consumable,
+        ItemCategory::Voucher,
+        ItemCategory::Deck,
+        ItemCategory::Edition,
+        ItemCategory::Blind,
+        ItemCategory::Tag,
+        ItemCategory::Booster,
+    ];
+
+    /// Returns the prefix of the keys of the items in the category
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ItemCategory::Joker => "j_",
+            ItemCategory::Consumable => "c_",
+            ItemCategory::Voucher => "v_",
+            ItemCategory::Deck => "b_",
+            ItemCategory::Edition => "e_",
+            ItemCategory::Blind => "bl_",
+            ItemCategory::Tag => "tag_",
+            ItemCategory::Booster => "p_",
+        }
+    }
+
+    /// Works out the category of an item from its key
+    pub fn of(name: &str) -> Option<Self> {
+        ItemCategory::ALL
+            .into_iter()
+            .find(|category| name.starts_with(category.prefix()))
+    }
+}
+
+impl fmt::Display for ItemCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ItemCategory::Joker => "Jokers",
+            ItemCategory::Consumable => "Consumables",
+            ItemCategory::Voucher => "Vouchers",
+            ItemCategory::Deck => "Decks",
+            ItemCategory::Edition => "Editions",
+            ItemCategory::Blind => "Blinds",
+            ItemCategory::Tag => "Tags",
+            ItemCategory::Booster => "Booster Packs",
+        };
+        write!(f, "{}", name)
+    }
+}
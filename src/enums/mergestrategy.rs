@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// Enum for the ways two meta files can be merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum MergeStrategy {
+    /// A flag is set if it is set in either file
+    #[default]
+    Union,
+    /// A flag is set only if it is set in both files
+    Intersection,
+    /// The items of the left file are kept as they are
+    PreferLeft,
+    /// The items of the right file replace those of the left
+    PreferRight,
+}
+
+impl MergeStrategy {
+    /// Every strategy, in the order the app lists them
+    pub const ALL: [MergeStrategy; 4] = [
+        MergeStrategy::Union,
+        MergeStrategy::Intersection,
+        MergeStrategy::PreferLeft,
+        MergeStrategy::PreferRight,
+    ];
+}
+
+impl fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MergeStrategy::Union => "Union",
+            MergeStrategy::Intersection => "Intersection",
+            MergeStrategy::PreferLeft => "Prefer Left",
+            MergeStrategy::PreferRight => "Prefer Right",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Enum for the flags every item in a meta file has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaFlag {
+    /// The badge that shows the item is new
+    Alerted,
+    /// Whether the item shows in the collection
+    Discovered,
+    /// Whether the item can show up in a run
+    Unlocked,
+}
+
+impl MetaFlag {
+    /// Every flag, in the order the app lists them
+    pub const ALL: [MetaFlag; 3] =
+        [MetaFlag::Alerted, MetaFlag::Discovered, MetaFlag::Unlocked];
+}
+
+impl fmt::Display for MetaFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MetaFlag::Alerted => "alerted",
+            MetaFlag::Discovered => "discovered",
+            MetaFlag::Unlocked => "unlocked",
+        };
+        write!(f, "{}", name)
+    }
+}
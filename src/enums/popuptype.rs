@@ -7,6 +7,11 @@ pub enum PopupType {
     ErrorLoad,
     /// The popup for confirming overwritting the current meta with defaults
     ConfirmMetaDefault,
-    /// The popup for confirming overwritting the current meta with a new file
-    ConfirmMetaFile,
+    /// The popup for confirming overwritting the current save with a new file
+    ConfirmOpenFile,
+    /// The popup for choosing between local edits and the game's changes
+    /// when the game writes the open file
+    ConfirmReload,
+    /// The popup for confirming a merge or preset, listing what it changes
+    ConfirmChanges,
 }
@@ -0,0 +1,108 @@
+use crate::enums::SaveKind;
+use crate::lua::{ParseError, SaveValue};
+use std::fmt;
+
+/// Enum for everything that can go wrong loading or saving a file
+#[derive(Debug)]
+pub enum SaveError {
+    /// The file could not be read or written
+    Io(std::io::Error),
+    /// The file is not compressed the way the game compresses saves
+    Decompress(std::io::Error),
+    /// The decompressed file is not valid UTF-8
+    Encoding(std::string::FromUtf8Error),
+    /// The decompressed file could not be parsed, the error has the position
+    Parse(ParseError),
+    /// The file is a different kind of save file than was asked for
+    WrongFileType {
+        /// The kind of file that was asked for
+        expected: SaveKind,
+        /// The kind of file it looks like instead
+        found: SaveKind,
+    },
+    /// A subtable the file needs is missing or is not a table
+    MissingSubtable(String),
+    /// The save could not be turned back into file data
+    Serialize(String),
+    /// An exported file could not be imported, or failed validation
+    Import(String),
+}
+
+impl SaveError {
+    /// Checks a decoded file is the kind of file that was asked for
+    /// Files that do not look like any kind are let through, so the missing
+    /// subtable is reported instead
+    pub fn check_kind(
+        value: &SaveValue,
+        expected: SaveKind,
+    ) -> Result<(), SaveError> {
+        if value.as_table().is_none() {
+            return Err(SaveError::WrongFileType {
+                expected,
+                found: SaveKind::Unknown,
+            });
+        }
+        match SaveKind::detect(value) {
+            SaveKind::Unknown => Ok(()),
+            found if found == expected => Ok(()),
+            found => Err(SaveError::WrongFileType { expected, found }),
+        }
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "File error: {}", err),
+            SaveError::Decompress(err) => {
+                write!(f, "Could not decompress the file: {}", err)
+            }
+            SaveError::Encoding(err) => {
+                write!(f, "The file is not valid text: {}", err)
+            }
+            SaveError::Parse(err) => write!(f, "{}", err),
+            SaveError::WrongFileType { expected, found: SaveKind::Unknown } => {
+                write!(f, "The file is not a {} file", expected)
+            }
+            SaveError::WrongFileType { expected, found } => {
+                write!(
+                    f,
+                    "Expected a {} file, found a {} file",
+                    expected, found
+                )
+            }
+            SaveError::MissingSubtable(name) => {
+                write!(f, "Subtable '{}' not found or not a table", name)
+            }
+            SaveError::Serialize(reason) => {
+                write!(f, "Could not write the save data: {}", reason)
+            }
+            SaveError::Import(reason) => {
+                write!(f, "Could not import the file: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(err) | SaveError::Decompress(err) => Some(err),
+            SaveError::Encoding(err) => Some(err),
+            SaveError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<ParseError> for SaveError {
+    fn from(err: ParseError) -> Self {
+        SaveError::Parse(err)
+    }
+}
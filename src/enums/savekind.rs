@@ -0,0 +1,55 @@
+use crate::lua::SaveValue;
+use std::fmt;
+
+/// Enum for the kinds of save files the game writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveKind {
+    /// The meta.jkr file, with the unlocks of a profile
+    Meta,
+    /// The profile.jkr file, with the stats of a profile
+    Profile,
+    /// The save.jkr file, with a run in progress
+    Save,
+    /// The settings.jkr file, shared by all profiles
+    Settings,
+    /// Any other file
+    Unknown,
+}
+
+impl SaveKind {
+    /// Works out which kind of save file a decoded file is from its top
+    /// level keys
+    pub fn detect(value: &SaveValue) -> Self {
+        let has_table = |key: &str| {
+            value.as_table().and_then(|table| table.get_table(key)).is_some()
+        };
+
+        if has_table("alerted")
+            && has_table("discovered")
+            && has_table("unlocked")
+        {
+            SaveKind::Meta
+        } else if has_table("career_stats") {
+            SaveKind::Profile
+        } else if has_table("GAME") && has_table("cardAreas") {
+            SaveKind::Save
+        } else if has_table("SOUND") {
+            SaveKind::Settings
+        } else {
+            SaveKind::Unknown
+        }
+    }
+}
+
+impl fmt::Display for SaveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SaveKind::Meta => "Meta",
+            SaveKind::Profile => "Profile",
+            SaveKind::Save => "Save",
+            SaveKind::Settings => "Settings",
+            SaveKind::Unknown => "Unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -1,13 +1,143 @@
+use crate::enums::{SaveError, SaveKind};
+use crate::lua::{self, SaveValue};
 use crate::saves::Meta;
 use crate::saves::Profile;
+use crate::saves::SAVEGAME_COUNTS;
+use crate::saves::SaveGame;
+use crate::saves::Settings;
+use crate::saves::mod_name;
 
+use std::path::Path;
+
+#[allow(clippy::large_enum_variant)]
 pub enum SaveType {
     /// Represents a Meta save type.
     Meta(Meta),
     // /// Represents a Profile save type.
     Profile(Profile),
-    // /// Represents a Savegame save type.
-    // Save(String)
+    /// Represents a Savegame save type.
+    Save(SaveGame),
+    /// Represents a Settings save type.
+    Settings(Settings),
+    /// Represents a file that is not one of the known save types.
+    Generic(SaveValue),
+}
+
+impl SaveType {
+    /// Reads the raw data of any save file into the matching SaveType
+    pub fn from_lua_data(data: Vec<u8>) -> Result<Self, SaveError> {
+        SaveType::from_save_value(lua::parse_save_data(&data)?)
+    }
+
+    /// Reads a decoded file into the SaveType matching its kind
+    /// Files that are not recognised are kept as a Generic SaveValue
+    pub fn from_save_value(value: SaveValue) -> Result<Self, SaveError> {
+        Ok(match SaveKind::detect(&value) {
+            SaveKind::Meta => SaveType::Meta(Meta::from_save_value(value)?),
+            SaveKind::Profile => {
+                SaveType::Profile(Profile::from_save_value(value)?)
+            }
+            SaveKind::Save => SaveType::Save(SaveGame::from_save_value(value)?),
+            SaveKind::Settings => {
+                SaveType::Settings(Settings::from_save_value(value)?)
+            }
+            SaveKind::Unknown => SaveType::Generic(value),
+        })
+    }
+
+    /// Reads a save file from disk, see from_lua_data
+    pub fn from_file(path: &Path) -> Result<Self, SaveError> {
+        SaveType::from_lua_data(std::fs::read(path)?)
+    }
+
+    /// Gives a meta file read from a path the game version of the run saved
+    /// next to it, see Meta::find_version
+    pub fn find_version(&mut self, path: &Path) {
+        if let SaveType::Meta(meta) = self {
+            meta.find_version(path);
+        }
+    }
+
+    /// Converts the save into the raw data that is written to its file
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        match self {
+            SaveType::Meta(meta) => meta.to_lua_data(),
+            SaveType::Profile(profile) => profile.to_lua_data(),
+            SaveType::Save(save) => save.to_lua_data(),
+            SaveType::Settings(settings) => settings.to_lua_data(),
+            SaveType::Generic(value) => value.to_lua_data(),
+        }
+    }
+
+    /// Builds a short summary of what is in the save, a line per value
+    pub fn summary(&self) -> String {
+        let mut out = format!("Kind: {}\n", self.kind());
+        match self {
+            SaveType::Meta(meta) => {
+                let version = meta.version().unwrap_or("unknown");
+                out.push_str(&format!("Version: {}\n", version));
+                if let Some(warning) = meta.version_warning() {
+                    out.push_str(&format!("Warning: {}\n", warning));
+                }
+                for stats in meta.stats() {
+                    out.push_str(&format!(
+                        "{}: {}/{} unlocked, {}/{} discovered, {}/{} alerted\n",
+                        stats.category,
+                        stats.unlocked.done,
+                        stats.unlocked.total,
+                        stats.discovered.done,
+                        stats.discovered.total,
+                        stats.alerted.done,
+                        stats.alerted.total
+                    ));
+                }
+                for mod_prefix in meta.mods() {
+                    out.push_str(&format!(
+                        "Mod {}: {} items\n",
+                        mod_name(&mod_prefix),
+                        meta.mod_items(&mod_prefix).len()
+                    ));
+                }
+            }
+            SaveType::Profile(profile) => {
+                let stats = &profile.career_stats;
+                out.push_str(&format!("Wins: {}\n", stats.wins));
+                out.push_str(&format!("Losses: {}\n", stats.losses));
+                out.push_str(&format!("Deck: {}\n", profile.memory.deck));
+            }
+            SaveType::Save(save) => {
+                out.push_str(&format!("Version: {}\n", save.version));
+                out.push_str(&format!("Deck: {}\n", save.deck));
+                out.push_str(&format!("Seed: {}\n", save.seed));
+                out.push_str(&format!("Dollars: {}\n", save.dollars));
+                for (value, (label, _)) in
+                    save.counts.iter().zip(SAVEGAME_COUNTS)
+                {
+                    out.push_str(&format!("{}: {}\n", label, value));
+                }
+            }
+            SaveType::Settings(settings) => {
+                out.push_str(&format!("Language: {}\n", settings.language));
+                out.push_str(&format!("Game Speed: {}\n", settings.game_speed));
+            }
+            SaveType::Generic(value) => {
+                let len = value.as_table().map_or(0, |table| table.len());
+                out.push_str(&format!("Top level keys: {}\n", len));
+            }
+        }
+        out
+    }
+
+    /// Returns the kind of save file this is
+    pub fn kind(&self) -> SaveKind {
+        match self {
+            SaveType::Meta(_) => SaveKind::Meta,
+            SaveType::Profile(_) => SaveKind::Profile,
+            SaveType::Save(_) => SaveKind::Save,
+            SaveType::Settings(_) => SaveKind::Settings,
+            SaveType::Generic(_) => SaveKind::Unknown,
+        }
+    }
 }
 
 impl From<SaveType> for Meta {
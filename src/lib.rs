@@ -2,5 +2,13 @@ pub mod saves;
 
 pub mod ui;
 
+pub mod backup;
+pub mod cli;
+pub mod config;
+pub mod diff;
 pub mod enums;
+pub mod locator;
 pub mod lua;
+pub mod presets;
+pub mod registry;
+pub mod watcher;
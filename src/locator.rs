@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+
+/// The Steam app id of Balatro
+pub const BALATRO_APP_ID: &str = "2379780";
+
+/// Where Balatro keeps its saves inside a Proton prefix
+const PREFIX_SAVE_DIR: &str =
+    "pfx/drive_c/users/steamuser/AppData/Roaming/Balatro";
+
+/// Where Steam can be installed, relative to the home directory
+/// The last two are the Flatpak install of Steam
+const STEAM_ROOTS: [&str; 5] = [
+    ".steam/steam",
+    ".steam/root",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/data/Steam",
+];
+
+/// The profile slots the game has
+pub const SLOTS: [u8; 3] = [1, 2, 3];
+
+/// A profile slot and the save files that are in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSlot {
+    /// The number of the slot, from 1 to 3
+    pub number: u8,
+    /// The directory of the slot
+    pub dir: PathBuf,
+    /// The meta.jkr file, if the slot has one
+    pub meta: Option<PathBuf>,
+    /// The profile.jkr file, if the slot has one
+    pub profile: Option<PathBuf>,
+    /// The save.jkr file of a run in progress, if the slot has one
+    pub save: Option<PathBuf>,
+}
+
+impl SaveSlot {
+    /// Looks for the save files of a slot in a Balatro save directory
+    /// Returns None if the slot has none of them
+    pub fn scan(root: &Path, number: u8) -> Option<Self> {
+        let dir = root.join(number.to_string());
+        let file = |name: &str| {
+            let path = dir.join(name);
+            path.is_file().then_some(path)
+        };
+        let slot = SaveSlot {
+            number,
+            meta: file("meta.jkr"),
+            profile: file("profile.jkr"),
+            save: file("save.jkr"),
+            dir: dir.clone(),
+        };
+        if slot.meta.is_none() && slot.profile.is_none() && slot.save.is_none()
+        {
+            return None;
+        }
+        Some(slot)
+    }
+}
+
+/// A Balatro save directory and the slots in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveLocation {
+    /// The Balatro directory, which holds the slots
+    pub root: PathBuf,
+    /// The settings.jkr file shared by the slots, if there is one
+    pub settings: Option<PathBuf>,
+    /// The slots that have any save files
+    pub slots: Vec<SaveSlot>,
+}
+
+impl SaveLocation {
+    /// Looks for the slots in a Balatro save directory
+    /// Returns None if there are no save files in it
+    pub fn scan(root: &Path) -> Option<Self> {
+        let settings = root.join("settings.jkr");
+        let location = SaveLocation {
+            root: root.to_path_buf(),
+            settings: settings.is_file().then_some(settings),
+            slots: SLOTS
+                .iter()
+                .filter_map(|number| SaveSlot::scan(root, *number))
+                .collect(),
+        };
+        if location.settings.is_none() && location.slots.is_empty() {
+            return None;
+        }
+        Some(location)
+    }
+}
+
+/// Finds every Balatro save directory of the current user
+/// This looks in the Proton prefix of every Steam library, including the
+/// Flatpak install of Steam and libraries added in Steam's settings
+pub fn find_save_locations() -> Vec<SaveLocation> {
+    match std::env::var_os("HOME") {
+        Some(home) => find_save_locations_in(Path::new(&home)),
+        None => Vec::new(),
+    }
+}
+
+/// Finds every Balatro save directory under a home directory
+pub fn find_save_locations_in(home: &Path) -> Vec<SaveLocation> {
+    let mut locations: Vec<SaveLocation> = Vec::new();
+    for library in steam_libraries(home) {
+        let root = library
+            .join("steamapps/compatdata")
+            .join(BALATRO_APP_ID)
+            .join(PREFIX_SAVE_DIR);
+        if let Some(location) = SaveLocation::scan(&root)
+            && !locations.iter().any(|l| same_dir(&l.root, &location.root))
+        {
+            locations.push(location);
+        }
+    }
+    locations
+}
+
+/// Finds the Steam libraries under a home directory
+/// The Steam installs are libraries themselves, and any other libraries are
+/// read from their libraryfolders.vdf
+pub fn steam_libraries(home: &Path) -> Vec<PathBuf> {
+    let mut libraries: Vec<PathBuf> = Vec::new();
+    for root in STEAM_ROOTS.iter().map(|root| home.join(root)) {
+        if !root.is_dir() {
+            continue;
+        }
+        let vdf = root.join("steamapps/libraryfolders.vdf");
+        let mut found = vec![root];
+        if let Ok(text) = std::fs::read_to_string(vdf) {
+            found.extend(library_folders(&text));
+        }
+        for library in found {
+            if library.is_dir()
+                && !libraries.iter().any(|l| same_dir(l, &library))
+            {
+                libraries.push(library);
+            }
+        }
+    }
+    libraries
+}
+
+/// A token of Valve's KeyValues format
+enum VdfToken {
+    /// A quoted string, either a key or a value
+    String(String),
+    /// The `{` that starts the section of a key
+    Open,
+    /// The `}` that ends a section
+    Close,
+}
+
+/// Reads the library paths out of the text of a libraryfolders.vdf file
+///
+/// The file is in Valve's KeyValues format, where every key and value is a
+/// quoted string. Each library has a "path" key, which is all this needs.
+pub fn library_folders(vdf: &str) -> Vec<PathBuf> {
+    let mut tokens = Vec::new();
+    let mut chars = vdf.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => {
+                let mut s = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                s.push(escaped);
+                            }
+                        }
+                        c => s.push(c),
+                    }
+                }
+                tokens.push(VdfToken::String(s));
+            }
+            _ => {}
+        }
+    }
+
+    // A key is followed by either its value or the section it opens, so
+    // taking the value with its key means a value is never read as a key
+    let mut paths = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let VdfToken::String(key) = token else {
+            continue;
+        };
+        if let Some(VdfToken::String(_)) = tokens.peek()
+            && let Some(VdfToken::String(value)) = tokens.next()
+            && key.eq_ignore_ascii_case("path")
+        {
+            paths.push(PathBuf::from(value));
+        }
+    }
+    paths
+}
+
+/// Checks if two paths are the same directory, following symlinks
+/// ~/.steam/steam is usually a symlink to ~/.local/share/Steam
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
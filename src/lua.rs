@@ -1,3 +1,14 @@
 mod lua_context;
 mod lua_meta;
+mod lua_parser;
+mod lua_writer;
+mod save_path;
+mod save_value;
 pub use lua_context::LuaContext;
+pub use lua_parser::{
+    MAX_DEPTH, MAX_INFLATED_SIZE, ParseError, ParseErrorKind, inflate, parse,
+    parse_save_data,
+};
+pub use lua_writer::{deflate, format_number, str_pack, to_save_data};
+pub use save_path::{PathError, format_path, parse_path};
+pub use save_value::{SaveKey, SaveTable, SaveValue};
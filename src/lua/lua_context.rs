@@ -1,5 +1,5 @@
+use crate::lua::{self, SaveKey, SaveValue};
 use mlua::{Lua, Table, Value};
-use std::io::Read;
 
 /// A struct to hold the lua LVM
 /// It is used because all lua tables only exists in their own lvm, so it allows for a
@@ -8,25 +8,63 @@ pub struct LuaContext {
     pub lua: Lua,
 }
 
+impl Default for LuaContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LuaContext {
     /// Creates a new LuaContext
     pub fn new() -> Self {
         LuaContext { lua: Lua::new() }
     }
     /// Loads a balatro save file into a lua table
+    /// Save files can come from anywhere, so the file is never evaluated.
+    /// It is parsed natively, which rejects anything but table constructors
+    /// and literal values, and the table is then built from the result.
     pub fn data_as_table(
         &self,
         data: Vec<u8>,
         tablename: &str,
     ) -> Result<Table, mlua::Error> {
-        let mut decoder = flate2::read::DeflateDecoder::new(&data[..]);
-        let mut s = String::new();
-        decoder.read_to_string(&mut s)?;
-        let lua = &self.lua;
-        let val: Table = lua.load(&s).eval()?;
-        lua.globals().set(tablename, &val)?;
+        let value =
+            lua::parse_save_data(&data).map_err(mlua::Error::external)?;
+        let val = match self.save_value_to_lua(&value)? {
+            Value::Table(table) => table,
+            _ => {
+                return Err(mlua::Error::RuntimeError(
+                    "Save file does not contain a table".to_string(),
+                ));
+            }
+        };
+        self.lua.globals().set(tablename, &val)?;
         Ok(val)
     }
+    /// Converts a SaveValue into a value in this lua VM
+    pub fn save_value_to_lua(
+        &self,
+        value: &SaveValue,
+    ) -> Result<Value, mlua::Error> {
+        let lua = &self.lua;
+        Ok(match value {
+            SaveValue::Table(save_table) => {
+                let table = lua.create_table()?;
+                for (key, value) in save_table.iter() {
+                    let value = self.save_value_to_lua(value)?;
+                    match key {
+                        SaveKey::Int(i) => table.raw_set(*i, value)?,
+                        SaveKey::Str(s) => table.raw_set(s.as_str(), value)?,
+                    }
+                }
+                Value::Table(table)
+            }
+            SaveValue::String(s) => Value::String(lua.create_string(s)?),
+            SaveValue::Integer(i) => Value::Integer(*i),
+            SaveValue::Float(f) => Value::Number(*f),
+            SaveValue::Bool(b) => Value::Boolean(*b),
+        })
+    }
     /// Accesses a subtable of a table
     pub fn access_subtable(
         &self,
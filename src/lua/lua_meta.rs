@@ -1,17 +1,19 @@
 use crate::lua::LuaContext;
+use crate::registry::{ItemEntry, registry};
+use crate::saves::ItemKey;
 use std::collections::HashSet;
 
-/// A function to parse an option and return a string representation
-fn option_parser(option: Option<bool>) -> String {
-    match option {
-        Some(true) => "Some(true)".to_string(),
-        Some(false) => "Some(false)".to_string(),
-        None => "None".to_string(),
-    }
+/// Checks if a key is from a mod, which are left out of the defaults
+/// That is any key ItemKey::parse finds a mod prefix for, so mods in the
+/// user's items files are left out as well as the KNOWN_MODS
+fn from_mod(name: &str) -> bool {
+    ItemKey::parse(name, registry()).mod_prefix.is_some()
 }
 
 impl LuaContext {
-    /// Print out the contents of a save file in the meta default format
+    /// Print out the items of a meta file as entries for assets/items.toml
+    /// The values printed are the ones in the file, so a new profile gives
+    /// the defaults
     pub fn make_meta_defaults(&self, data: Vec<u8>) -> Result<(), mlua::Error> {
         let t = self.data_as_table(data, "map_table")?;
 
@@ -23,10 +25,7 @@ impl LuaContext {
         for pair in alerted_table.pairs::<String, bool>() {
             match pair {
                 Ok((name, _)) => {
-                    if name.contains("cry_")
-                        || name.contains("mp_")
-                        || name.contains("mtg_")
-                    {
+                    if from_mod(&name) {
                         continue;
                     }
                     set.insert(name);
@@ -40,10 +39,7 @@ impl LuaContext {
         for pair in discovered_table.pairs::<String, bool>() {
             match pair {
                 Ok((name, _)) => {
-                    if name.contains("cry_")
-                        || name.contains("mp_")
-                        || name.contains("mtg_")
-                    {
+                    if from_mod(&name) {
                         continue;
                     }
                     set.insert(name);
@@ -57,10 +53,7 @@ impl LuaContext {
         for pair in unlocked_table.pairs::<String, bool>() {
             match pair {
                 Ok((name, _)) => {
-                    if name.contains("cry_")
-                        || name.contains("mp_")
-                        || name.contains("mtg_")
-                    {
+                    if from_mod(&name) {
                         continue;
                     }
                     set.insert(name);
@@ -92,14 +85,23 @@ impl LuaContext {
                 } else {
                     None
                 };
-            let alerted = option_parser(alerted);
-            let discovered = option_parser(discovered);
-            let unlocked = option_parser(unlocked);
-
-            println!(
-                "(\"{}\", {}, {}, {}),",
-                name, alerted, discovered, unlocked
-            );
+            let key = ItemKey::parse(name, registry());
+            let Some(category) = key.category() else {
+                eprintln!("Skipped {}, its category is not known", name);
+                continue;
+            };
+            let entry = ItemEntry {
+                key: name.clone(),
+                category,
+                mod_prefix: key.mod_prefix.map(str::to_string),
+                alerted,
+                discovered,
+                unlocked,
+            };
+            match toml::Value::try_from(entry) {
+                Ok(value) => println!("    {},", value),
+                Err(err) => eprintln!("Error writing {}: {}", name, err),
+            }
         }
 
         Ok(())
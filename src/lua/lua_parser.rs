@@ -0,0 +1,661 @@
+use crate::enums::SaveError;
+use crate::lua::{SaveKey, SaveTable, SaveValue};
+use std::fmt;
+use std::io::Read;
+
+/// The largest a save file is allowed to be once inflated
+/// Real files are well under a megabyte, so anything past this is a
+/// decompression bomb rather than a save
+pub const MAX_INFLATED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The deepest tables are allowed to be nested
+/// Save files nest a handful of levels, this only guards the parser's stack
+pub const MAX_DEPTH: usize = 128;
+
+/// The kinds of problems a save file can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The file is not well formed
+    Syntax,
+    /// The file contains something other than table constructors and literal
+    /// values, such as a variable, a function call or an operator
+    Code,
+    /// The file is larger or nested deeper than a save file can be
+    Limit,
+}
+
+/// An error found while parsing a save file
+/// The line and column are 1-based, the column is counted in bytes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// What kind of error this is
+    pub kind: ParseErrorKind,
+    /// The line the error was found on
+    pub line: usize,
+    /// The column the error was found on
+    pub column: usize,
+    /// What went wrong
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.kind {
+            ParseErrorKind::Syntax => "Parse error",
+            ParseErrorKind::Code => "Save file contains code",
+            ParseErrorKind::Limit => "Save file exceeds limits",
+        };
+        write!(
+            f,
+            "{} at line {}, column {}: {}",
+            prefix, self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Inflates a balatro save file and parses it into a SaveValue
+/// This never runs any lua, see parse for what is accepted
+pub fn parse_save_data(data: &[u8]) -> Result<SaveValue, SaveError> {
+    let s = inflate(data)?;
+    Ok(parse(&s)?)
+}
+
+/// Inflates a balatro save file into its text
+/// Fails if the text would be larger than MAX_INFLATED_SIZE
+pub fn inflate(data: &[u8]) -> Result<String, SaveError> {
+    let decoder = flate2::read::DeflateDecoder::new(data);
+    let mut bytes = Vec::new();
+    decoder
+        .take(MAX_INFLATED_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(SaveError::Decompress)?;
+    if bytes.len() as u64 > MAX_INFLATED_SIZE {
+        return Err(SaveError::Parse(ParseError {
+            kind: ParseErrorKind::Limit,
+            line: 1,
+            column: 1,
+            message: format!(
+                "Inflated data is larger than {} bytes",
+                MAX_INFLATED_SIZE
+            ),
+        }));
+    }
+    String::from_utf8(bytes).map_err(SaveError::Encoding)
+}
+
+/// Parses the text of a save file into a SaveValue
+///
+/// The game writes its files with STR_PACK, which only produces
+/// `return {["key"]=value,...}` where the values are tables, strings, numbers
+/// and booleans. That is the only grammar this accepts, anything else is an
+/// error rather than being evaluated. Variables, function calls and
+/// operators are reported as ParseErrorKind::Code.
+pub fn parse(text: &str) -> Result<SaveValue, ParseError> {
+    let mut parser = Parser::new(text);
+    parser.skip_whitespace()?;
+    if parser.peek_word() == Some("return") {
+        parser.pos += "return".len();
+    }
+    let value = match parser.parse_value()? {
+        Some(value) => value,
+        None => return Err(parser.error("Expected a value, found nil")),
+    };
+    parser.skip_whitespace()?;
+    if parser.peek() == Some(b';') {
+        parser.pos += 1;
+        parser.skip_whitespace()?;
+    }
+    if parser.pos < parser.src.len() {
+        return Err(parser.unexpected("Expected end of file"));
+    }
+    Ok(value)
+}
+
+/// A recursive descent parser over the bytes of a save file
+struct Parser<'a> {
+    /// The text being parsed
+    src: &'a [u8],
+    /// The current position in the text
+    pos: usize,
+    /// How many tables deep the current position is
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a new Parser at the start of the text
+    fn new(text: &'a str) -> Self {
+        Parser { src: text.as_bytes(), pos: 0, depth: 0 }
+    }
+
+    /// Creates an error at the current position
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    /// Creates a syntax error at a position
+    fn error_at(&self, pos: usize, message: impl Into<String>) -> ParseError {
+        self.error_kind_at(ParseErrorKind::Syntax, pos, message)
+    }
+
+    /// Creates an error for something that is not allowed at this position
+    /// If it looks like lua code rather than a typo, it is reported as such
+    fn unexpected(&self, message: impl Into<String>) -> ParseError {
+        let is_code = match self.peek() {
+            Some(b'(' | b'.' | b':' | b'+' | b'*' | b'/' | b'%' | b'^') => true,
+            Some(b'#' | b'=' | b'<' | b'>' | b'~' | b'&' | b'|') => true,
+            Some(b'-') => self.peek_next() != Some(b'-'),
+            _ => self.peek_word().is_some(),
+        };
+        if is_code {
+            self.error_kind_at(
+                ParseErrorKind::Code,
+                self.pos,
+                "Only table constructors and literal values are allowed",
+            )
+        } else {
+            self.error(message)
+        }
+    }
+
+    /// Creates an error of a kind at a position
+    /// The line and column are only worked out here, since errors are rare
+    fn error_kind_at(
+        &self,
+        kind: ParseErrorKind,
+        pos: usize,
+        message: impl Into<String>,
+    ) -> ParseError {
+        let pos = pos.min(self.src.len());
+        let before = &self.src[..pos];
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        ParseError {
+            kind,
+            line,
+            column: pos - line_start + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the current byte without consuming it
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    /// Returns the byte after the current one without consuming it
+    fn peek_next(&self) -> Option<u8> {
+        self.src.get(self.pos + 1).copied()
+    }
+
+    /// Returns the identifier at the current position without consuming it
+    fn peek_word(&self) -> Option<&'a str> {
+        let start = self.pos;
+        let first = *self.src.get(start)?;
+        if !(first.is_ascii_alphabetic() || first == b'_') {
+            return None;
+        }
+        let len = self.src[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+            .count();
+        // Identifiers are ascii, so this is always on a char boundary
+        std::str::from_utf8(&self.src[start..start + len]).ok()
+    }
+
+    /// Consumes a byte if it matches, returning an error otherwise
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(format!("Expected '{}'", byte as char)))
+        }
+    }
+
+    /// Skips whitespace and comments
+    fn skip_whitespace(&mut self) -> Result<(), ParseError> {
+        loop {
+            match self.peek() {
+                Some(b) if b.is_ascii_whitespace() => self.pos += 1,
+                Some(b'-') if self.peek_next() == Some(b'-') => {
+                    let start = self.pos;
+                    self.pos += 2;
+                    if let Some(level) = self.long_bracket_level() {
+                        self.read_long_bracket(level).map_err(|_| {
+                            self.error_at(start, "Unfinished comment")
+                        })?;
+                    } else {
+                        while let Some(b) = self.peek() {
+                            if b == b'\n' {
+                                break;
+                            }
+                            self.pos += 1;
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Checks if the current position opens a long bracket like `[==[`
+    /// Returns the number of `=` in it
+    fn long_bracket_level(&self) -> Option<usize> {
+        if self.peek() != Some(b'[') {
+            return None;
+        }
+        let level =
+            self.src[self.pos + 1..].iter().take_while(|&&b| b == b'=').count();
+        if self.src.get(self.pos + 1 + level) == Some(&b'[') {
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    /// Reads the contents of a long bracket, the opening bracket is consumed too
+    fn read_long_bracket(&mut self, level: usize) -> Result<&'a [u8], ()> {
+        self.pos += level + 2;
+        // A newline right after the opening bracket is skipped
+        if self.peek() == Some(b'\r') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'\n') {
+            self.pos += 1;
+        }
+        let start = self.pos;
+        while self.pos < self.src.len() {
+            if self.src[self.pos] == b']'
+                && self.src[self.pos + 1..]
+                    .iter()
+                    .take(level)
+                    .all(|&b| b == b'=')
+                && self.src.get(self.pos + 1 + level) == Some(&b']')
+            {
+                let contents = &self.src[start..self.pos];
+                self.pos += level + 2;
+                return Ok(contents);
+            }
+            self.pos += 1;
+        }
+        Err(())
+    }
+
+    /// Parses a value
+    /// nil is valid lua but is returned as None since it can not be stored
+    fn parse_value(&mut self) -> Result<Option<SaveValue>, ParseError> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(b'{') => Ok(Some(SaveValue::Table(self.parse_table()?))),
+            Some(b'"') | Some(b'\'') => {
+                Ok(Some(SaveValue::String(self.parse_string()?)))
+            }
+            Some(b'[') if self.long_bracket_level().is_some() => {
+                Ok(Some(SaveValue::String(self.parse_long_string()?)))
+            }
+            Some(b) if b.is_ascii_digit() || b == b'.' || b == b'-' => {
+                Ok(Some(self.parse_number()?))
+            }
+            Some(_) => match self.peek_word() {
+                Some("true") => {
+                    self.pos += 4;
+                    Ok(Some(SaveValue::Bool(true)))
+                }
+                Some("false") => {
+                    self.pos += 5;
+                    Ok(Some(SaveValue::Bool(false)))
+                }
+                Some("nil") => {
+                    self.pos += 3;
+                    Ok(None)
+                }
+                Some(word) => Err(self.error_kind_at(
+                    ParseErrorKind::Code,
+                    self.pos,
+                    format!(
+                        "Found '{}', only literal values are allowed",
+                        word
+                    ),
+                )),
+                None => Err(self.unexpected("Expected a value")),
+            },
+            None => Err(self.error("Expected a value, found end of file")),
+        }
+    }
+
+    /// Parses a table constructor
+    fn parse_table(&mut self) -> Result<SaveTable, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error_kind_at(
+                ParseErrorKind::Limit,
+                self.pos,
+                format!("Tables are nested more than {} deep", MAX_DEPTH),
+            ));
+        }
+        self.depth += 1;
+        let table = self.parse_table_contents();
+        self.depth -= 1;
+        table
+    }
+
+    /// Parses the contents of a table constructor, see parse_table
+    fn parse_table_contents(&mut self) -> Result<SaveTable, ParseError> {
+        self.expect(b'{')?;
+        let mut table = SaveTable::new();
+        // Positional values are numbered from 1 like in lua
+        let mut next_index = 1;
+
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(table);
+                }
+                None => {
+                    return Err(self.error("Unfinished table, expected '}'"));
+                }
+                _ => {}
+            }
+
+            let key_pos = self.pos;
+            let key = if self.peek() == Some(b'[')
+                && self.long_bracket_level().is_none()
+            {
+                self.pos += 1;
+                let key = match self.parse_value()? {
+                    Some(key) => key,
+                    None => {
+                        return Err(
+                            self.error_at(key_pos, "Table key can not be nil")
+                        );
+                    }
+                };
+                self.skip_whitespace()?;
+                self.expect(b']')?;
+                self.skip_whitespace()?;
+                self.expect(b'=')?;
+                Some(self.to_key(key, key_pos)?)
+            } else if let Some(word) = self.peek_word()
+                && !matches!(word, "true" | "false" | "nil")
+            {
+                self.pos += word.len();
+                self.skip_whitespace()?;
+                self.expect(b'=')?;
+                Some(SaveKey::Str(word.to_string()))
+            } else {
+                None
+            };
+
+            let value = self.parse_value()?;
+            let key = match key {
+                Some(key) => key,
+                None => {
+                    let key = SaveKey::Int(next_index);
+                    next_index += 1;
+                    key
+                }
+            };
+            if let Some(value) = value {
+                table.insert(key, value);
+            }
+
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',') | Some(b';') => self.pos += 1,
+                Some(b'}') => {}
+                None => {
+                    return Err(self.error("Unfinished table, expected '}'"));
+                }
+                _ => return Err(self.unexpected("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// Converts a value into a table key
+    fn to_key(
+        &self,
+        key: SaveValue,
+        pos: usize,
+    ) -> Result<SaveKey, ParseError> {
+        match key {
+            SaveValue::String(s) => Ok(SaveKey::Str(s)),
+            SaveValue::Integer(i) => Ok(SaveKey::Int(i)),
+            // Lua normalises float keys with no fractional part to integers
+            SaveValue::Float(f)
+                if f.fract() == 0.0
+                    && f >= i64::MIN as f64
+                    && f < i64::MAX as f64 =>
+            {
+                Ok(SaveKey::Int(f as i64))
+            }
+            _ => {
+                Err(self
+                    .error_at(pos, "Table keys must be strings or integers"))
+            }
+        }
+    }
+
+    /// Parses a quoted string, handling lua escape sequences
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let quote = self.src[self.pos];
+        self.pos += 1;
+        let mut bytes = Vec::new();
+
+        loop {
+            let Some(b) = self.peek() else {
+                return Err(self.error_at(start, "Unfinished string"));
+            };
+            self.pos += 1;
+            match b {
+                _ if b == quote => break,
+                b'\\' => self.parse_escape(&mut bytes)?,
+                b'\n' | b'\r' => {
+                    return Err(self.error_at(start, "Unfinished string"));
+                }
+                _ => bytes.push(b),
+            }
+        }
+
+        String::from_utf8(bytes)
+            .map_err(|_| self.error_at(start, "String is not valid UTF-8"))
+    }
+
+    /// Parses an escape sequence, the backslash is already consumed
+    fn parse_escape(&mut self, bytes: &mut Vec<u8>) -> Result<(), ParseError> {
+        let escape_pos = self.pos - 1;
+        let Some(b) = self.peek() else {
+            return Err(self.error("Unfinished string"));
+        };
+        self.pos += 1;
+        match b {
+            b'a' => bytes.push(0x07),
+            b'b' => bytes.push(0x08),
+            b'f' => bytes.push(0x0c),
+            b'n' => bytes.push(b'\n'),
+            b'r' => bytes.push(b'\r'),
+            b't' => bytes.push(b'\t'),
+            b'v' => bytes.push(0x0b),
+            b'\\' | b'"' | b'\'' => bytes.push(b),
+            b'\n' => {
+                bytes.push(b'\n');
+                if self.peek() == Some(b'\r') {
+                    self.pos += 1;
+                }
+            }
+            b'\r' => {
+                bytes.push(b'\n');
+                if self.peek() == Some(b'\n') {
+                    self.pos += 1;
+                }
+            }
+            b'x' => {
+                let hex = self.src.get(self.pos..self.pos + 2).and_then(|h| {
+                    u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok()
+                });
+                match hex {
+                    Some(value) => {
+                        bytes.push(value);
+                        self.pos += 2;
+                    }
+                    None => {
+                        return Err(self.error_at(
+                            escape_pos,
+                            "Invalid hexadecimal escape sequence",
+                        ));
+                    }
+                }
+            }
+            b'z' => {
+                while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+                    self.pos += 1;
+                }
+            }
+            b'u' => {
+                let value = self.parse_unicode_escape().ok_or_else(|| {
+                    self.error_at(escape_pos, "Invalid unicode escape sequence")
+                })?;
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(value.encode_utf8(&mut buf).as_bytes());
+            }
+            b'0'..=b'9' => {
+                let mut value = (b - b'0') as u32;
+                for _ in 0..2 {
+                    match self.peek() {
+                        Some(d) if d.is_ascii_digit() => {
+                            value = value * 10 + (d - b'0') as u32;
+                            self.pos += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                if value > 255 {
+                    return Err(
+                        self.error_at(escape_pos, "Decimal escape too large")
+                    );
+                }
+                bytes.push(value as u8);
+            }
+            _ => {
+                return Err(
+                    self.error_at(escape_pos, "Invalid escape sequence")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the `{XXX}` part of a `\u{XXX}` escape
+    fn parse_unicode_escape(&mut self) -> Option<char> {
+        if self.peek() != Some(b'{') {
+            return None;
+        }
+        let len = self.src[self.pos + 1..]
+            .iter()
+            .take_while(|b| b.is_ascii_hexdigit())
+            .count();
+        if len == 0 || self.src.get(self.pos + 1 + len) != Some(&b'}') {
+            return None;
+        }
+        let digits =
+            std::str::from_utf8(&self.src[self.pos + 1..self.pos + 1 + len])
+                .ok()?;
+        let value = char::from_u32(u32::from_str_radix(digits, 16).ok()?)?;
+        self.pos += len + 2;
+        Some(value)
+    }
+
+    /// Parses a long string like `[[text]]`
+    fn parse_long_string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let level = self.long_bracket_level().unwrap_or(0);
+        let contents = self
+            .read_long_bracket(level)
+            .map_err(|_| self.error_at(start, "Unfinished long string"))?;
+        String::from_utf8(contents.to_vec())
+            .map_err(|_| self.error_at(start, "String is not valid UTF-8"))
+    }
+
+    /// Parses a number, including a leading minus sign
+    fn parse_number(&mut self) -> Result<SaveValue, ParseError> {
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+            self.skip_whitespace()?;
+        }
+        let number_start = self.pos;
+        // Only digits can follow the minus sign, so -inf or -x are code
+        if !self.peek().is_some_and(|b| b.is_ascii_digit() || b == b'.') {
+            return Err(self.unexpected("Malformed number"));
+        }
+
+        let is_hex = self.peek() == Some(b'0')
+            && matches!(self.peek_next(), Some(b'x') | Some(b'X'));
+        if is_hex {
+            self.pos += 2;
+        }
+        while let Some(b) = self.peek() {
+            let exponent = if is_hex {
+                matches!(b, b'p' | b'P')
+            } else {
+                matches!(b, b'e' | b'E')
+            };
+            if exponent {
+                self.pos += 1;
+                if matches!(self.peek(), Some(b'+') | Some(b'-')) {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_alphanumeric() || b == b'.' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        // Numbers are ascii, so this is always on a char boundary
+        let text = std::str::from_utf8(&self.src[number_start..self.pos])
+            .unwrap_or_default();
+        let value = if is_hex {
+            let digits = &text[2..];
+            // Like lua, hex integers that do not fit wrap around
+            digits
+                .chars()
+                .try_fold(0i64, |acc, c| {
+                    let digit = c.to_digit(16)? as i64;
+                    Some(acc.wrapping_mul(16).wrapping_add(digit))
+                })
+                .filter(|_| !digits.is_empty())
+                .map(SaveValue::Integer)
+        } else if text.bytes().all(|b| b.is_ascii_digit()) && !text.is_empty() {
+            Some(match text.parse::<i64>() {
+                Ok(i) => SaveValue::Integer(i),
+                // Lua falls back to a float when an integer does not fit
+                Err(_) => {
+                    SaveValue::Float(text.parse().unwrap_or(f64::INFINITY))
+                }
+            })
+        } else {
+            text.parse::<f64>().ok().map(SaveValue::Float)
+        };
+
+        match value {
+            Some(SaveValue::Integer(i)) if negative => {
+                Ok(SaveValue::Integer(i.wrapping_neg()))
+            }
+            Some(SaveValue::Float(f)) if negative => Ok(SaveValue::Float(-f)),
+            Some(value) => Ok(value),
+            None => {
+                Err(self
+                    .error_at(start, format!("Malformed number '{}'", text)))
+            }
+        }
+    }
+}
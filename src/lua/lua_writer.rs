@@ -0,0 +1,135 @@
+use crate::enums::SaveError;
+use crate::lua::{SaveKey, SaveTable, SaveValue};
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// Serializes a SaveValue and deflates it, giving the raw data of a save file
+pub fn to_save_data(value: &SaveValue) -> Result<Vec<u8>, SaveError> {
+    deflate(&str_pack(value))
+}
+
+/// Deflates the text of a save file
+/// The default level is used, as the editor always has. The game inflates
+/// any level, and only the text is checked against what the game writes.
+pub fn deflate(text: &str) -> Result<Vec<u8>, SaveError> {
+    let mut encoder = flate2::write::DeflateEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    );
+    encoder
+        .write_all(text.as_bytes())
+        .and_then(|_| encoder.finish())
+        .map_err(|err| SaveError::Serialize(err.to_string()))
+}
+
+/// Serializes a SaveValue into the text of a save file
+///
+/// This is a port of the game's STR_PACK function, so the output is the same
+/// as the game would write for the same table: `return ` followed by the
+/// table, every entry written as `[key]=value,` with no whitespace.
+/// Entries are written in the order they are stored in the table.
+pub fn str_pack(value: &SaveValue) -> String {
+    let mut out = String::from("return ");
+    write_value(&mut out, value);
+    out
+}
+
+/// Writes a value the way STR_PACK does
+fn write_value(out: &mut String, value: &SaveValue) {
+    match value {
+        SaveValue::Table(table) => write_table(out, table),
+        SaveValue::String(s) => write_quoted(out, s),
+        SaveValue::Integer(i) => {
+            let _ = write!(out, "{}", i);
+        }
+        SaveValue::Float(f) => out.push_str(&format_number(*f)),
+        SaveValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+    }
+}
+
+/// Writes a table the way STR_PACK does, including the trailing comma
+fn write_table(out: &mut String, table: &SaveTable) {
+    out.push('{');
+    for (key, value) in table.iter() {
+        out.push('[');
+        match key {
+            SaveKey::Str(s) => write_quoted(out, s),
+            SaveKey::Int(i) => {
+                let _ = write!(out, "{}", i);
+            }
+        }
+        out.push_str("]=");
+        write_value(out, value);
+        out.push(',');
+    }
+    out.push('}');
+}
+
+/// Writes a string quoted the way `string.format("%q", s)` does in LuaJIT
+///
+/// Quotes, backslashes and newlines are escaped with a backslash, other
+/// control characters as decimal escapes. The decimal escape is padded to
+/// three digits when a digit follows it, so it can not be misread.
+fn write_quoted(out: &mut String, s: &str) {
+    out.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\\' | '\n' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii_control() => {
+                let next_is_digit =
+                    chars.peek().is_some_and(|next| next.is_ascii_digit());
+                let code = c as u32;
+                if next_is_digit {
+                    let _ = write!(out, "\\{:03}", code);
+                } else {
+                    let _ = write!(out, "\\{}", code);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Formats a number the way LuaJIT's tostring does, which is `%.14g`
+///
+/// LuaJIT has no integer type, so a float with no fractional part is written
+/// without a decimal point, exactly like an integer would be.
+pub fn format_number(f: f64) -> String {
+    if f.is_nan() {
+        return "nan".to_string();
+    }
+    if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    if f == 0.0 {
+        return if f.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    // Rounding to 14 significant digits first gives the exponent %g uses
+    let scientific = format!("{:.13e}", f);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or(("", "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    if (-4..14).contains(&exponent) {
+        let decimals = (13 - exponent) as usize;
+        trim_fraction(&format!("{:.*}", decimals, f)).to_string()
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim_fraction(mantissa), sign, exponent.abs())
+    }
+}
+
+/// Removes trailing zeros after the decimal point, and the point if it is
+/// left on its own
+fn trim_fraction(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
@@ -0,0 +1,151 @@
+use crate::lua::SaveKey;
+use std::fmt;
+
+/// An error from using a path into a SaveValue
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    /// The path could not be parsed, the string says why
+    Invalid(String),
+    /// A part of the path that needs to be a table is not one
+    NotATable(String),
+    /// The path is empty, so it points at the root value
+    Empty,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::Invalid(reason) => write!(f, "Invalid path: {}", reason),
+            PathError::NotATable(path) => {
+                write!(f, "'{}' is not a table", path)
+            }
+            PathError::Empty => write!(f, "Path is empty"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Parses a path like `GAME.dollars` or `cardAreas.jokers.cards[1]`
+///
+/// Names are separated with dots, and integer keys are written in brackets.
+/// Keys that are not plain names can be quoted in brackets, like
+/// `["key with spaces"]`, with `\\` and `\"` for backslashes and quotes.
+pub fn parse_path(path: &str) -> Result<Vec<SaveKey>, PathError> {
+    let bytes = path.as_bytes();
+    let mut keys = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'.' if keys.is_empty() => {
+                return Err(PathError::Invalid(
+                    "Path can not start with '.'".to_string(),
+                ));
+            }
+            b'.' => {
+                pos += 1;
+                if pos >= bytes.len() || matches!(bytes[pos], b'[' | b'.') {
+                    return Err(PathError::Invalid(
+                        "Expected a name after '.'".to_string(),
+                    ));
+                }
+            }
+            b'[' => {
+                let (key, end) = parse_bracket(path, pos)?;
+                keys.push(key);
+                pos = end;
+                continue;
+            }
+            _ => {}
+        }
+
+        if pos < bytes.len() && bytes[pos] != b'[' {
+            if !keys.is_empty() && bytes[pos - 1] != b'.' {
+                return Err(PathError::Invalid(format!(
+                    "Expected '.' or '[' at position {}",
+                    pos
+                )));
+            }
+            let len = path[pos..].find(['.', '[']).unwrap_or(path.len() - pos);
+            keys.push(SaveKey::Str(path[pos..pos + len].to_string()));
+            pos += len;
+        }
+    }
+
+    if keys.is_empty() {
+        return Err(PathError::Empty);
+    }
+    Ok(keys)
+}
+
+/// Parses a bracketed key like `[1]` or `["name"]` starting at pos
+/// Returns the key and the position after the closing bracket
+fn parse_bracket(
+    path: &str,
+    pos: usize,
+) -> Result<(SaveKey, usize), PathError> {
+    let missing = || PathError::Invalid("Missing closing ']'".to_string());
+    let inner = &path[pos + 1..];
+    let trimmed = inner.trim_start();
+    let Some(quoted) = trimmed.strip_prefix('"') else {
+        let end = inner.find(']').ok_or_else(missing)?;
+        let inner = inner[..end].trim();
+        return match inner.parse::<i64>() {
+            Ok(i) => Ok((SaveKey::Int(i), pos + 1 + end + 1)),
+            Err(_) => Err(PathError::Invalid(format!(
+                "'[{}]' must be an integer or a quoted string",
+                inner
+            ))),
+        };
+    };
+
+    // The key runs to the first quote that is not escaped
+    let mut key = String::new();
+    let mut chars = quoted.char_indices();
+    let close = loop {
+        let c = match chars.next() {
+            Some((i, '"')) => break i,
+            Some((_, '\\')) => chars.next(),
+            other => other,
+        };
+        match c {
+            Some((_, c)) => key.push(c),
+            None => {
+                return Err(PathError::Invalid(
+                    "Missing closing '\"'".to_string(),
+                ));
+            }
+        }
+    };
+    let rest = quoted[close + 1..].trim_start();
+    if !rest.starts_with(']') {
+        return Err(missing());
+    }
+    Ok((SaveKey::Str(key), path.len() - rest.len() + 1))
+}
+
+/// Formats keys back into a path that parse_path accepts
+pub fn format_path(keys: &[SaveKey]) -> String {
+    let mut path = String::new();
+    for key in keys {
+        match key {
+            SaveKey::Int(i) => path.push_str(&format!("[{}]", i)),
+            SaveKey::Str(s)
+                if !s.is_empty()
+                    && !s.contains(['.', '[', ']', '"'])
+                    && !s.starts_with(char::is_whitespace) =>
+            {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(s);
+            }
+            SaveKey::Str(s) => {
+                let escaped = s.replace('\\', "\\\\").replace('"', "\\\"");
+                path.push_str(&format!("[\"{}\"]", escaped));
+            }
+        }
+    }
+    path
+}
@@ -0,0 +1,323 @@
+use crate::enums::SaveError;
+use crate::lua::{self, PathError, format_path, parse_path};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A key of a save table
+/// The game only ever writes string keys and integer keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SaveKey {
+    /// An integer key, written as `[1]`
+    Int(i64),
+    /// A string key, written as `["name"]`
+    Str(String),
+}
+
+impl fmt::Display for SaveKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveKey::Int(i) => write!(f, "{}", i),
+            SaveKey::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<&str> for SaveKey {
+    fn from(key: &str) -> Self {
+        SaveKey::Str(key.to_string())
+    }
+}
+
+impl From<String> for SaveKey {
+    fn from(key: String) -> Self {
+        SaveKey::Str(key)
+    }
+}
+
+impl From<i64> for SaveKey {
+    fn from(key: i64) -> Self {
+        SaveKey::Int(key)
+    }
+}
+
+/// An owned value read from a save file
+///
+/// Any .jkr file can be read into a SaveValue and written back out without
+/// losing anything, so it is what the typed save structs are built on.
+/// Values inside it can be reached with paths, see parse_path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveValue {
+    /// A table of key value pairs
+    Table(SaveTable),
+    /// A string
+    String(String),
+    /// A number without a fractional part
+    Integer(i64),
+    /// A number with a fractional part
+    Float(f64),
+    /// A boolean
+    Bool(bool),
+}
+
+impl SaveValue {
+    /// Reads the raw data of a save file into a SaveValue
+    pub fn from_lua_data(data: &[u8]) -> Result<Self, SaveError> {
+        lua::parse_save_data(data)
+    }
+    /// Converts the value into the raw data that is used in a save file
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        lua::to_save_data(self)
+    }
+    /// Returns the name of the type of the value, as lua would call it
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            SaveValue::Table(_) => "table",
+            SaveValue::String(_) => "string",
+            SaveValue::Integer(_) | SaveValue::Float(_) => "number",
+            SaveValue::Bool(_) => "boolean",
+        }
+    }
+    /// Gets the value at a path like `GAME.dollars`
+    /// Returns None if the path is invalid or nothing is there
+    pub fn get_path(&self, path: &str) -> Option<&SaveValue> {
+        let keys = parse_path(path).ok()?;
+        self.get_keys(&keys)
+    }
+    /// Gets the value at a path that is already split into keys
+    pub fn get_keys(&self, keys: &[SaveKey]) -> Option<&SaveValue> {
+        let mut value = self;
+        for key in keys {
+            value = value.as_table()?.get(key.clone())?;
+        }
+        Some(value)
+    }
+    /// Gets a mutable reference to the value at a path
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut SaveValue> {
+        let keys = parse_path(path).ok()?;
+        let mut value = self;
+        for key in keys {
+            value = value.as_table_mut()?.get_mut(key)?;
+        }
+        Some(value)
+    }
+    /// Sets the value at a path like `cardAreas.jokers.cards[1]`
+    ///
+    /// Missing tables along the path are created, but existing values that
+    /// are not tables are never replaced. Returns the old value, if any.
+    pub fn set_path(
+        &mut self,
+        path: &str,
+        new_value: SaveValue,
+    ) -> Result<Option<SaveValue>, PathError> {
+        let keys = parse_path(path)?;
+        let (last, parents) = keys.split_last().ok_or(PathError::Empty)?;
+        let mut value = self;
+        for (i, key) in parents.iter().enumerate() {
+            let table = value
+                .as_table_mut()
+                .ok_or_else(|| PathError::NotATable(format_path(&keys[..i])))?;
+            if !table.contains_key(key.clone()) {
+                table.insert(key.clone(), SaveValue::Table(SaveTable::new()));
+            }
+            value = table.get_mut(key.clone()).unwrap(); // Safe to unwrap because it was just inserted
+        }
+        let table = value
+            .as_table_mut()
+            .ok_or_else(|| PathError::NotATable(format_path(parents)))?;
+        Ok(table.insert(last.clone(), new_value))
+    }
+    /// Removes the value at a path, returning it
+    pub fn remove_path(&mut self, path: &str) -> Option<SaveValue> {
+        let keys = parse_path(path).ok()?;
+        let (last, parents) = keys.split_last()?;
+        let mut value = self;
+        for key in parents {
+            value = value.as_table_mut()?.get_mut(key.clone())?;
+        }
+        value.as_table_mut()?.remove(last.clone())
+    }
+    /// Returns the table if the value is one
+    pub fn as_table(&self) -> Option<&SaveTable> {
+        match self {
+            SaveValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+    /// Returns a mutable reference to the table if the value is one
+    pub fn as_table_mut(&mut self) -> Option<&mut SaveTable> {
+        match self {
+            SaveValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+    /// Returns the string if the value is one
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SaveValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    /// Returns the boolean if the value is one
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SaveValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+    /// Returns the number as an integer
+    /// Floats are only returned if they have no fractional part
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            SaveValue::Integer(i) => Some(*i),
+            SaveValue::Float(f) if f.fract() == 0.0 => Some(*f as i64),
+            _ => None,
+        }
+    }
+    /// Returns the number as a float
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SaveValue::Integer(i) => Some(*i as f64),
+            SaveValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+    /// Returns the value the way lua would treat it in a condition
+    /// Only false is falsy since a save file can not contain nil
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, SaveValue::Bool(false))
+    }
+}
+
+/// A table from a save file
+/// The entries keep the order they were read in, so a file that is read and
+/// written again comes out the same
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SaveTable {
+    /// The entries, in the order they were read
+    entries: Vec<(SaveKey, SaveValue)>,
+    /// Where each key is in entries
+    index: HashMap<SaveKey, usize>,
+}
+
+impl SaveTable {
+    /// Creates a new empty SaveTable
+    pub fn new() -> Self {
+        SaveTable::default()
+    }
+    /// Gets the value of a key
+    pub fn get(&self, key: impl Into<SaveKey>) -> Option<&SaveValue> {
+        self.index.get(&key.into()).map(|&i| &self.entries[i].1)
+    }
+    /// Gets a mutable reference to the value of a key
+    pub fn get_mut(
+        &mut self,
+        key: impl Into<SaveKey>,
+    ) -> Option<&mut SaveValue> {
+        self.index.get(&key.into()).map(|&i| &mut self.entries[i].1)
+    }
+    /// Gets the value of a key if it is a table
+    pub fn get_table(&self, key: impl Into<SaveKey>) -> Option<&SaveTable> {
+        self.get(key).and_then(SaveValue::as_table)
+    }
+    /// Accesses a subtable of the table
+    /// This mirrors LuaContext::access_subtable
+    pub fn access_subtable(
+        &self,
+        subtable_name: &str,
+    ) -> Result<&SaveTable, SaveError> {
+        if let Some(subtable) = self.get_table(subtable_name) {
+            return Ok(subtable);
+        }
+
+        Err(SaveError::MissingSubtable(subtable_name.to_string()))
+    }
+    /// Checks if the table has a key
+    pub fn contains_key(&self, key: impl Into<SaveKey>) -> bool {
+        self.index.contains_key(&key.into())
+    }
+    /// Sets the value of a key, returning the old value if there was one
+    /// An existing key keeps its position, a new key is added at the end
+    pub fn insert(
+        &mut self,
+        key: impl Into<SaveKey>,
+        value: SaveValue,
+    ) -> Option<SaveValue> {
+        let key = key.into();
+        match self.index.get(&key) {
+            Some(&i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+    /// Gets a subtable to write into, creating it if it is missing
+    /// A value under the key that is not a table is replaced
+    pub fn subtable_mut(&mut self, key: impl Into<SaveKey>) -> &mut SaveTable {
+        let key = key.into();
+        if self.get_table(key.clone()).is_none() {
+            self.insert(key.clone(), SaveValue::Table(SaveTable::new()));
+        }
+        // Safe to unwrap because we just made sure it is a table
+        self.get_mut(key).and_then(SaveValue::as_table_mut).unwrap()
+    }
+    /// Sets a number, leaving the table alone if it already holds it
+    /// Whole numbers are stored as integers, as the game can not tell them
+    /// apart from floats anyway
+    pub fn set_number(&mut self, key: impl Into<SaveKey>, value: f64) {
+        let key = key.into();
+        if self.get(key.clone()).and_then(SaveValue::as_f64) == Some(value) {
+            return;
+        }
+        let value = if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+            SaveValue::Integer(value as i64)
+        } else {
+            SaveValue::Float(value)
+        };
+        self.insert(key, value);
+    }
+    /// Sets a string, leaving the table alone if it already holds it
+    pub fn set_string(&mut self, key: impl Into<SaveKey>, value: &str) {
+        let key = key.into();
+        if self.get(key.clone()).and_then(SaveValue::as_str) != Some(value) {
+            self.insert(key, SaveValue::String(value.to_string()));
+        }
+    }
+    /// Sets a boolean, leaving the table alone if it already holds it
+    pub fn set_bool(&mut self, key: impl Into<SaveKey>, value: bool) {
+        let key = key.into();
+        if self.get(key.clone()).and_then(SaveValue::as_bool) != Some(value) {
+            self.insert(key, SaveValue::Bool(value));
+        }
+    }
+    /// Removes a key from the table, returning its value
+    pub fn remove(&mut self, key: impl Into<SaveKey>) -> Option<SaveValue> {
+        let index = self.index.remove(&key.into())?;
+        // The entries after it move down one
+        for i in self.index.values_mut() {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        Some(self.entries.remove(index).1)
+    }
+    /// Iterates over the entries in the order they were read
+    pub fn iter(&self) -> impl Iterator<Item = (&SaveKey, &SaveValue)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+    /// Iterates mutably over the entries in the order they were read
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&SaveKey, &mut SaveValue)> {
+        self.entries.iter_mut().map(|(k, v)| (&*k, v))
+    }
+    /// Returns the number of entries in the table
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Checks if the table has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,9 +1,25 @@
-use bal_save::ui::MyApp;
+use bal_save::ui::{MyApp, WINDOW_TITLE};
+use std::process::ExitCode;
 
-fn main() -> eframe::Result<()> {
+fn main() -> ExitCode {
+    // Any arguments mean the command line interface is being used
+    if std::env::args_os().len() > 1 {
+        return bal_save::cli::run();
+    }
+
+    match run_editor() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_editor() -> eframe::Result<()> {
     let mut native_options = eframe::NativeOptions::default();
     let viewport =
-        egui::ViewportBuilder::default().with_title("bal_save").with_icon(
+        egui::ViewportBuilder::default().with_title(WINDOW_TITLE).with_icon(
             eframe::icon_data::from_png_bytes(
                 &include_bytes!("assets/temp_logo.png")[..],
             )
@@ -12,7 +28,7 @@ fn main() -> eframe::Result<()> {
 
     native_options.viewport = viewport;
     eframe::run_native(
-        "bal_save",
+        WINDOW_TITLE,
         native_options,
         Box::new(|cc| Ok(Box::new(MyApp::new(cc)))),
     )
@@ -0,0 +1,289 @@
+use crate::config;
+use crate::enums::{ItemCategory, MetaFlag, SaveError};
+use crate::registry::Registry;
+use crate::saves::{Meta, MetaEdit};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// What a preset starts from, before its items and rules are applied
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetBase {
+    /// The meta it is applied to, as it is
+    #[default]
+    Current,
+    /// A new profile, see the registry
+    Defaults,
+    /// Every item the registry knows unlocked, any others are left alone
+    Complete,
+}
+
+/// The flags of an item in a preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetFlags {
+    /// Whether the item is alerted
+    pub alerted: bool,
+    /// Whether the item is discovered
+    pub discovered: bool,
+    /// Whether the item is unlocked
+    pub unlocked: bool,
+}
+
+/// A rule that sets flags on every item it matches
+/// Flags that are not given are left as they are
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PresetRule {
+    /// Matches items whose key starts with this, like j_
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Matches the item with this key, like j_blueprint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// What to set alerted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alerted: Option<bool>,
+    /// What to set discovered to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovered: Option<bool>,
+    /// What to set unlocked to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocked: Option<bool>,
+    /// Only matches items of the base game, the ones in the registry that
+    /// are not from a mod
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub vanilla: bool,
+}
+
+impl PresetRule {
+    /// Checks if the rule applies to an item, using the registry of the
+    /// meta it is from for vanilla rules
+    /// A rule with neither a prefix nor a key matches every item
+    pub fn matches(&self, name: &str, registry: &Registry) -> bool {
+        let prefix = self.prefix.as_ref().is_none_or(|p| name.starts_with(p));
+        let key = self.key.as_ref().is_none_or(|k| name == k);
+        let vanilla = !self.vanilla
+            || registry.get(name).is_some_and(|e| e.mod_prefix.is_none());
+        prefix && key && vanilla
+    }
+}
+
+/// A named meta state that can be applied to a loaded meta
+///
+/// A preset starts from its base, then sets the items it captured, then
+/// applies its rules in order, so later rules win.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Preset {
+    /// The name the preset is listed and saved under
+    pub name: String,
+    /// What the preset starts from
+    #[serde(default)]
+    pub base: PresetBase,
+    /// Flags captured from a meta, by item key
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub items: BTreeMap<String, PresetFlags>,
+    /// Rules applied after the captured items, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<PresetRule>,
+}
+
+impl Preset {
+    /// Creates a preset of only rules, starting from a base
+    pub fn from_rules(
+        name: &str,
+        base: PresetBase,
+        rules: Vec<PresetRule>,
+    ) -> Self {
+        Preset { name: name.to_string(), base, items: BTreeMap::new(), rules }
+    }
+
+    /// Creates a preset that captures every item of a meta as it is
+    pub fn capture(name: &str, meta: &Meta) -> Self {
+        let items = meta
+            .items()
+            .map(|(key, item)| {
+                let flags = PresetFlags {
+                    alerted: item.alerted,
+                    discovered: item.discovered,
+                    unlocked: item.unlocked,
+                };
+                (key.clone(), flags)
+            })
+            .collect();
+        Preset {
+            name: name.to_string(),
+            base: PresetBase::Current,
+            items,
+            rules: Vec::new(),
+        }
+    }
+
+    /// The presets that come with the app
+    pub fn builtin() -> Vec<Preset> {
+        let all = |prefix: &str, value: bool| PresetRule {
+            prefix: Some(prefix.to_string()),
+            discovered: Some(value),
+            unlocked: Some(value),
+            ..PresetRule::default()
+        };
+        vec![
+            Preset::from_rules("Fresh Start", PresetBase::Defaults, Vec::new()),
+            Preset::from_rules(
+                "Vanilla 100%",
+                PresetBase::Complete,
+                Vec::new(),
+            ),
+            Preset::from_rules(
+                "All Jokers, No Vouchers",
+                PresetBase::Defaults,
+                vec![all("j_", true), all("v_", false)],
+            ),
+            // Every player gets the same pool of jokers, consumables,
+            // vouchers and decks, with no badges left to click through
+            // Like Vanilla 100%, modded items are left alone
+            Preset::from_rules(
+                "Tournament Ruleset",
+                PresetBase::Defaults,
+                [
+                    all("j_", true),
+                    all("c_", true),
+                    all("v_", true),
+                    all("b_", true),
+                    PresetRule {
+                        alerted: Some(false),
+                        ..PresetRule::default()
+                    },
+                ]
+                .into_iter()
+                .map(|rule| PresetRule { vanilla: true, ..rule })
+                .collect(),
+            ),
+        ]
+    }
+
+    /// Applies the preset to a meta
+    /// Captured items that the meta does not have are skipped
+    pub fn apply(&self, meta: &mut Meta) {
+        match self.base {
+            PresetBase::Current => {}
+            PresetBase::Defaults => {
+                for category in ItemCategory::ALL {
+                    meta.reset_category(category);
+                }
+            }
+            PresetBase::Complete => {
+                // unlock_all_type would turn on every flag of items it does
+                // not know, which modded items may not have
+                for entry in meta.registry().entries() {
+                    if let Some(item) = meta.get_item(&entry.key) {
+                        for flag in MetaFlag::ALL {
+                            item.set_flag(flag, true);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (key, flags) in self.items.iter() {
+            if let Some(item) = meta.get_item(key) {
+                item.set_flag(MetaFlag::Alerted, flags.alerted);
+                item.set_flag(MetaFlag::Discovered, flags.discovered);
+                item.set_flag(MetaFlag::Unlocked, flags.unlocked);
+            }
+        }
+
+        let names: Vec<String> = meta.items().map(|(n, _)| n.clone()).collect();
+        let registry = meta.registry();
+        for rule in self.rules.iter() {
+            let flags = [
+                (MetaFlag::Alerted, rule.alerted),
+                (MetaFlag::Discovered, rule.discovered),
+                (MetaFlag::Unlocked, rule.unlocked),
+            ];
+            for name in names.iter().filter(|name| rule.matches(name, registry))
+            {
+                let Some(item) = meta.get_item(name) else {
+                    continue;
+                };
+                for (flag, value) in flags {
+                    if let Some(value) = value {
+                        item.set_flag(flag, value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Works out what applying the preset to a meta would change
+    pub fn changes(&self, meta: &Meta) -> MetaEdit {
+        meta.preview(|meta| self.apply(meta))
+    }
+
+    /// Saves the preset to a directory, as a TOML file named after it
+    /// Fails if another preset is saved as the same file, like "A b" and
+    /// "A_b", rather than replacing it
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, SaveError> {
+        let text = toml::to_string(self)
+            .map_err(|err| SaveError::Serialize(err.to_string()))?;
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.toml", file_stem(&self.name)));
+        if let Some(other) = read_preset(&path)
+            && other.name != self.name
+        {
+            return Err(SaveError::Io(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "'{}' would replace the preset '{}', pick another name",
+                    self.name, other.name
+                ),
+            )));
+        }
+        std::fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    /// Deletes the saved file of the preset from a directory
+    pub fn delete(&self, dir: &Path) -> Result<(), SaveError> {
+        let path = dir.join(format!("{}.toml", file_stem(&self.name)));
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
+
+/// Returns the directory presets are saved in, like
+/// ~/.config/bal-save/presets on Linux
+pub fn presets_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("presets"))
+}
+
+/// Reads the presets saved in a directory, sorted by name
+/// Files that are not valid presets are skipped
+pub fn list_presets(dir: &Path) -> Vec<Preset> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut presets: Vec<Preset> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| read_preset(&path))
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+    presets
+}
+
+/// Reads a saved preset, None if the file is missing or not a preset
+fn read_preset(path: &Path) -> Option<Preset> {
+    let text = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&text).ok()
+}
+
+/// Turns a preset name into a safe file name
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
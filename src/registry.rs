@@ -0,0 +1,365 @@
+use crate::config;
+use crate::enums::{ItemCategory, MetaFlag, SaveError};
+use crate::saves::MetaItem;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The items that come with the app, see assets/items.toml
+const BUNDLED_ITEMS: &str = include_str!("assets/items.toml");
+
+/// The registries the app uses, see registries
+static REGISTRIES: OnceLock<Registries> = OnceLock::new();
+
+/// An item the registry knows about
+///
+/// Each flag is None if the item can not have it, or the value it has in a
+/// new profile if it can.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemEntry {
+    /// The key of the item, like j_blueprint
+    pub key: String,
+    /// The category the item is listed under
+    pub category: ItemCategory,
+    /// The prefix of the mod the item is from, None for vanilla items
+    #[serde(default, rename = "mod", skip_serializing_if = "Option::is_none")]
+    pub mod_prefix: Option<String>,
+    /// Whether the item is alerted in a new profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alerted: Option<bool>,
+    /// Whether the item is discovered in a new profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovered: Option<bool>,
+    /// Whether the item is unlocked in a new profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocked: Option<bool>,
+}
+
+impl ItemEntry {
+    /// Returns the value a flag has in a new profile, None if the item can
+    /// not have it
+    pub fn default_flag(&self, flag: MetaFlag) -> Option<bool> {
+        match flag {
+            MetaFlag::Alerted => self.alerted,
+            MetaFlag::Discovered => self.discovered,
+            MetaFlag::Unlocked => self.unlocked,
+        }
+    }
+
+    /// Returns whether the item can have a flag
+    pub fn can_have(&self, flag: MetaFlag) -> bool {
+        self.default_flag(flag).is_some()
+    }
+
+    /// Returns whether a new profile has the item at all, which is when it
+    /// has any flag set
+    pub fn in_new_profile(&self) -> bool {
+        MetaFlag::ALL
+            .into_iter()
+            .any(|flag| self.default_flag(flag) == Some(true))
+    }
+
+    /// Returns the item as it is in a new profile
+    pub fn default_item(&self) -> MetaItem {
+        MetaItem::new(self.alerted, self.discovered, self.unlocked)
+            .with_mod(self.mod_prefix.as_deref())
+    }
+}
+
+/// The layout of a registry file
+#[derive(Debug, Serialize, Deserialize)]
+struct RegistryFile {
+    /// The game version the items are for, None for files of modded items
+    /// that are the same in every version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    items: Vec<ItemEntry>,
+}
+
+/// The items that are known for a game version, read from the bundled items
+/// and any files the user adds
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    /// The game version the items are for, like 1.0.1, which covers every
+    /// build of it, like 1.0.1o-FULL
+    version: Option<String>,
+    /// The entries, in the order they were read
+    entries: Vec<ItemEntry>,
+    /// Where each key is in entries
+    index: HashMap<String, usize>,
+    /// The prefixes of the mods the entries are from
+    mods: HashSet<String>,
+}
+
+impl Registry {
+    /// Reads a registry from the text of a registry file
+    pub fn from_toml(text: &str) -> Result<Self, SaveError> {
+        let file: RegistryFile = toml::from_str(text)
+            .map_err(|err| SaveError::Import(err.to_string()))?;
+        let mut registry =
+            Registry { version: file.version, ..Registry::default() };
+        for entry in file.items {
+            registry.insert(entry);
+        }
+        Ok(registry)
+    }
+
+    /// Returns the items that come with the app
+    pub fn bundled() -> Self {
+        Registry::from_toml(BUNDLED_ITEMS).expect("the bundled items are valid")
+    }
+
+    /// Returns the game version the items are for
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Checks if the items are for a version of the game
+    /// A registry for 1.0.1 covers 1.0.1o-FULL, but not 1.0.10, and one for
+    /// 1.1 covers every 1.1.x
+    pub fn covers(&self, version: &str) -> bool {
+        let Some(rest) =
+            self.version.as_deref().and_then(|v| version.strip_prefix(v))
+        else {
+            return false;
+        };
+        !rest.starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    /// Adds the entries of another registry, replacing any with the same key
+    pub fn extend(&mut self, other: Registry) {
+        for entry in other.entries {
+            self.insert(entry);
+        }
+    }
+
+    /// Adds an entry, replacing any with the same key
+    pub fn insert(&mut self, entry: ItemEntry) {
+        if let Some(mod_prefix) = &entry.mod_prefix {
+            self.mods.insert(mod_prefix.clone());
+        }
+        match self.index.get(&entry.key) {
+            Some(&i) => self.entries[i] = entry,
+            None => {
+                self.index.insert(entry.key.clone(), self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Returns the entry of an item
+    pub fn get(&self, key: &str) -> Option<&ItemEntry> {
+        self.index.get(key).map(|&i| &self.entries[i])
+    }
+
+    /// Returns whether an item is known
+    pub fn contains(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Returns whether any entry is from a mod
+    pub fn knows_mod(&self, mod_prefix: &str) -> bool {
+        self.mods.contains(mod_prefix)
+    }
+
+    /// Returns every entry, in the order they were read
+    pub fn entries(&self) -> impl Iterator<Item = &ItemEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the entries of a category
+    pub fn category(
+        &self,
+        category: ItemCategory,
+    ) -> impl Iterator<Item = &ItemEntry> {
+        self.entries.iter().filter(move |entry| entry.category == category)
+    }
+
+    /// Returns how many items are known
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no items are known
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The registries of every game version there are items for
+#[derive(Debug, Clone)]
+pub struct Registries {
+    /// The registries, the bundled one first
+    registries: Vec<Registry>,
+    /// The directory the user's items files were read from, if any
+    dir: Option<PathBuf>,
+    /// The files in the user's items directory that could not be read, with
+    /// why
+    skipped: Vec<(PathBuf, String)>,
+}
+
+impl Registries {
+    /// Returns the registries that come with the app
+    pub fn bundled() -> Self {
+        Registries {
+            registries: vec![Registry::bundled()],
+            dir: None,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Returns the bundled registries with the files in the user's items
+    /// directory read over them, see load_from
+    pub fn load() -> Self {
+        match items_dir() {
+            Some(dir) => Registries::load_from(&dir),
+            None => Registries::bundled(),
+        }
+    }
+
+    /// Returns the bundled registries with the files in a directory read
+    /// over them
+    /// Files that can not be read are skipped, so a bad file never stops the
+    /// app from starting, see skipped
+    pub fn load_from(dir: &Path) -> Self {
+        let mut registries = Registries::bundled();
+        registries.skipped = registries
+            .extend_from_dir(dir)
+            .into_iter()
+            .map(|(path, err)| (path, err.to_string()))
+            .collect();
+        registries.dir = Some(dir.to_path_buf());
+        registries
+    }
+
+    /// Returns the directory the items files were read from, None for the
+    /// bundled registries
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns the files that were skipped by load, with why
+    pub fn skipped(&self) -> &[(PathBuf, String)] {
+        &self.skipped
+    }
+
+    /// Reads every .toml file in a directory over the registries, in order
+    /// of their names
+    ///
+    /// A file without a version is read over every registry. A file for a
+    /// version there is no registry for yet starts one, from a copy of the
+    /// bundled items. Returns the files that could not be read, with their
+    /// errors.
+    pub fn extend_from_dir(&mut self, dir: &Path) -> Vec<(PathBuf, SaveError)> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let read = std::fs::read_to_string(&path)
+                .map_err(SaveError::from)
+                .and_then(|text| Registry::from_toml(&text));
+            match read {
+                Ok(other) => self.extend(other),
+                Err(err) => errors.push((path, err)),
+            }
+        }
+        errors
+    }
+
+    /// Adds the entries of a registry to the registry of its version
+    pub fn extend(&mut self, other: Registry) {
+        let Some(version) = other.version.clone() else {
+            for registry in self.registries.iter_mut() {
+                registry.extend(other.clone());
+            }
+            return;
+        };
+        match self
+            .registries
+            .iter_mut()
+            .find(|registry| registry.version() == Some(version.as_str()))
+        {
+            Some(registry) => registry.extend(other),
+            None => {
+                let mut registry = self.base().clone();
+                registry.version = Some(version);
+                registry.extend(other);
+                self.registries.push(registry);
+            }
+        }
+    }
+
+    /// Returns the registry used when there are no items for a version,
+    /// which is the bundled one
+    pub fn base(&self) -> &Registry {
+        &self.registries[0]
+    }
+
+    /// Returns the registry for a game version, the most specific one if
+    /// several cover it
+    /// Files with no version or one there are no items for use the base
+    pub fn for_version(&self, version: Option<&str>) -> &Registry {
+        version.and_then(|version| self.find(version)).unwrap_or(self.base())
+    }
+
+    /// Checks if there are items for a game version
+    pub fn knows(&self, version: &str) -> bool {
+        self.find(version).is_some()
+    }
+
+    /// Returns the versions there are items for
+    pub fn versions(&self) -> impl Iterator<Item = &str> {
+        self.registries.iter().filter_map(Registry::version)
+    }
+
+    /// Finds the most specific registry that covers a game version
+    fn find(&self, version: &str) -> Option<&Registry> {
+        self.registries
+            .iter()
+            .filter(|registry| registry.covers(version))
+            .max_by_key(|registry| registry.version().map_or(0, str::len))
+    }
+}
+
+/// Returns the registries the app uses, loaded the first time they are
+/// needed unless init_registries gave them first
+pub fn registries() -> &'static Registries {
+    REGISTRIES.get_or_init(Registries::load)
+}
+
+/// Sets the registries the app uses, instead of the ones load gives
+/// Returns false if they were already set or loaded, in which case nothing
+/// changes
+pub fn init_registries(registries: Registries) -> bool {
+    REGISTRIES.set(registries).is_ok()
+}
+
+/// Makes the app use only the bundled items, so the user's items files are
+/// never read, like tests need
+/// Returns false if items files were already read, so it has to be called
+/// before anything that reads items
+pub fn use_bundled() -> bool {
+    REGISTRIES.get_or_init(Registries::bundled).dir().is_none()
+}
+
+/// Returns the registry used for files with no game version, see
+/// Registries::base
+pub fn registry() -> &'static Registry {
+    registries().base()
+}
+
+/// Returns the directory the user's items files are read from, like
+/// ~/.config/bal-save/items on Linux
+pub fn items_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("items"))
+}
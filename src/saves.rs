@@ -1,10 +1,21 @@
-pub mod defaults;
 #[cfg(feature = "dev")]
 mod dev;
+mod history;
 mod meta;
 mod profile;
+mod savegame;
+mod settings;
 
 #[cfg(feature = "dev")]
 pub use dev::DevTest;
-pub use meta::Meta;
-pub use profile::Profile;
+pub use history::{HISTORY_LIMIT, History};
+pub use meta::{
+    CategoryStats, FlagCount, ItemChange, ItemKey, KNOWN_MODS, Meta, MetaEdit,
+    MetaItem, is_known_mod, mod_name,
+};
+pub use profile::{
+    CareerStats, ChallengeProgress, ConsumableUsage, DeckUsage, HandUsage,
+    HighScore, JokerUsage, Memory, Profile, Progress, Tally,
+};
+pub use savegame::{SAVEGAME_COUNTS, SaveGame};
+pub use settings::{GAME_SPEEDS, LANGUAGES, Settings};
@@ -1,548 +0,0 @@
-/// A meta save with 100% completion
-pub const ALL_META: [(&str, Option<bool>, Option<bool>, Option<bool>); 342] = [
-    // name, alerted, discovered, unlocked
-    ("j_satellite", Some(true), Some(true), Some(true)),
-    ("bl_ox", Some(true), Some(true), None),
-    ("bl_club", Some(true), Some(true), None),
-    ("j_sly", Some(true), Some(true), Some(true)),
-    ("b_abandoned", Some(true), Some(true), Some(true)),
-    ("c_eris", Some(true), Some(true), None),
-    ("j_acrobat", Some(true), Some(true), Some(true)),
-    ("j_even_steven", Some(true), Some(true), Some(true)),
-    ("j_merry_andy", Some(true), Some(true), Some(true)),
-    ("p_buffoon_mega_1", Some(true), Some(true), None),
-    ("j_vagabond", Some(true), Some(true), Some(true)),
-    ("tag_polychrome", Some(true), Some(true), None),
-    ("j_clever", Some(true), Some(true), Some(true)),
-    ("b_yellow", Some(true), Some(true), Some(true)),
-    ("v_grabber", Some(true), Some(true), Some(true)),
-    ("c_lovers", Some(true), Some(true), None),
-    ("b_black", Some(true), Some(true), Some(true)),
-    ("j_castle", Some(true), Some(true), Some(true)),
-    ("p_celestial_normal_4", Some(true), Some(true), None),
-    ("j_popcorn", Some(true), Some(true), Some(true)),
-    ("c_ouija", Some(true), Some(true), Some(true)),
-    ("v_overstock_norm", Some(true), Some(true), Some(true)),
-    ("p_arcana_normal_3", Some(true), Some(true), None),
-    ("bl_head", Some(true), Some(true), None),
-    ("c_judgement", Some(true), Some(true), None),
-    ("j_square", Some(true), Some(true), Some(true)),
-    ("v_tarot_merchant", Some(true), Some(true), Some(true)),
-    ("tag_investment", Some(true), Some(true), None),
-    ("c_ceres", Some(true), Some(true), None),
-    ("p_celestial_jumbo_1", Some(true), Some(true), None),
-    ("j_arrowhead", Some(true), Some(true), Some(true)),
-    ("j_walkie_talkie", Some(true), Some(true), Some(true)),
-    ("tag_double", Some(true), Some(true), None),
-    ("j_perkeo", Some(true), Some(true), Some(true)),
-    ("j_smeared", Some(true), Some(true), Some(true)),
-    ("j_loyalty_card", Some(true), Some(true), Some(true)),
-    ("c_devil", Some(true), Some(true), None),
-    ("tag_meteor", Some(true), Some(true), None),
-    ("c_familiar", Some(true), Some(true), Some(true)),
-    ("b_checkered", Some(true), Some(true), Some(true)),
-    ("p_arcana_normal_1", Some(true), Some(true), None),
-    ("j_rocket", Some(true), Some(true), Some(true)),
-    ("p_standard_normal_2", Some(true), Some(true), None),
-    ("j_madness", Some(true), Some(true), Some(true)),
-    ("j_trading", Some(true), Some(true), Some(true)),
-    ("j_selzer", Some(true), Some(true), Some(true)),
-    ("c_wheel_of_fortune", Some(true), Some(true), None),
-    ("j_swashbuckler", Some(true), Some(true), Some(true)),
-    ("v_telescope", Some(true), Some(true), Some(true)),
-    ("j_greedy_joker", Some(true), Some(true), Some(true)),
-    ("j_drivers_license", Some(true), Some(true), Some(true)),
-    ("j_seeing_double", Some(true), Some(true), Some(true)),
-    ("j_to_the_moon", Some(true), Some(true), Some(true)),
-    ("b_zodiac", Some(true), Some(true), Some(true)),
-    ("j_photograph", Some(true), Some(true), Some(true)),
-    ("c_planet_x", Some(true), Some(true), None),
-    ("j_stuntman", Some(true), Some(true), Some(true)),
-    ("j_gros_michel", Some(true), Some(true), Some(true)),
-    ("c_strength", Some(true), Some(true), Some(true)),
-    ("v_reroll_glut", Some(true), Some(true), Some(true)),
-    ("j_reserved_parking", Some(true), Some(true), Some(true)),
-    ("tag_juggle", Some(true), Some(true), None),
-    ("bl_mouth", Some(true), Some(true), None),
-    ("j_supernova", Some(true), Some(true), Some(true)),
-    ("j_cloud_9", Some(true), Some(true), Some(true)),
-    ("j_ancient", Some(true), Some(true), Some(true)),
-    ("j_bull", Some(true), Some(true), Some(true)),
-    ("j_caino", Some(true), Some(true), Some(true)),
-    ("c_base", None, Some(true), None),
-    ("j_diet_cola", Some(true), Some(true), Some(true)),
-    ("v_illusion", Some(true), Some(true), Some(true)),
-    ("j_bloodstone", Some(true), Some(true), Some(true)),
-    ("c_jupiter", Some(true), Some(true), None),
-    ("j_crazy", Some(true), Some(true), Some(true)),
-    ("j_mystic_summit", Some(true), Some(true), Some(true)),
-    ("j_wily", Some(true), Some(true), Some(true)),
-    ("c_aura", Some(true), Some(true), None),
-    ("p_celestial_normal_3", Some(true), Some(true), None),
-    ("j_wrathful_joker", Some(true), Some(true), Some(true)),
-    ("v_tarot_tycoon", Some(true), Some(true), Some(true)),
-    ("j_matador", Some(true), Some(true), Some(true)),
-    ("j_blackboard", Some(true), Some(true), Some(true)),
-    ("tag_ethereal", Some(true), Some(true), None),
-    ("e_base", Some(true), Some(true), Some(true)),
-    ("bl_fish", Some(true), Some(true), None),
-    ("b_nebula", Some(true), Some(true), Some(true)),
-    ("p_buffoon_normal_2", Some(true), Some(true), None),
-    ("c_deja_vu", Some(true), Some(true), None),
-    ("b_anaglyph", Some(true), Some(true), Some(true)),
-    ("j_troubadour", Some(true), Some(true), Some(true)),
-    ("bl_manacle", Some(true), Some(true), None),
-    ("bl_final_vessel", Some(true), Some(true), None),
-    ("bl_plant", Some(true), Some(true), None),
-    ("j_tribe", Some(true), Some(true), Some(true)),
-    ("j_ticket", Some(true), Some(true), Some(true)),
-    ("bl_wheel", Some(true), Some(true), None),
-    ("v_hieroglyph", Some(true), Some(true), Some(true)),
-    ("j_erosion", Some(true), Some(true), Some(true)),
-    ("p_buffoon_normal_1", Some(true), Some(true), None),
-    ("bl_wall", Some(true), Some(true), None),
-    ("c_moon", Some(true), Some(true), None),
-    ("tag_orbital", Some(true), Some(true), None),
-    ("j_hiker", Some(true), Some(true), Some(true)),
-    ("p_arcana_jumbo_1", Some(true), Some(true), None),
-    ("j_blue_joker", Some(true), Some(true), Some(true)),
-    ("j_marble", Some(true), Some(true), Some(true)),
-    ("tag_uncommon", Some(true), Some(true), None),
-    ("j_todo_list", Some(true), Some(true), Some(true)),
-    ("j_invisible", Some(true), Some(true), Some(true)),
-    ("j_sixth_sense", Some(true), Some(true), Some(true)),
-    ("v_magic_trick", Some(true), Some(true), Some(true)),
-    ("j_blueprint", Some(true), Some(true), Some(true)),
-    ("j_yorick", Some(true), Some(true), Some(true)),
-    ("c_mercury", Some(true), Some(true), None),
-    ("v_paint_brush", Some(true), Some(true), Some(true)),
-    ("tag_buffoon", Some(true), Some(true), None),
-    ("j_runner", Some(true), Some(true), Some(true)),
-    ("c_sun", Some(true), Some(true), None),
-    ("c_hanged_man", Some(true), Some(true), None),
-    ("j_wee", Some(true), Some(true), Some(true)),
-    ("p_celestial_normal_1", Some(true), Some(true), None),
-    ("j_hack", Some(true), Some(true), Some(true)),
-    ("c_world", Some(true), Some(true), None),
-    ("j_delayed_grat", Some(true), Some(true), Some(true)),
-    ("e_polychrome", Some(true), Some(true), Some(true)),
-    ("p_spectral_jumbo_1", Some(true), Some(true), None),
-    ("v_palette", Some(true), Some(true), Some(true)),
-    ("b_challenge", Some(true), None, Some(true)),
-    ("c_wraith", Some(true), Some(true), None),
-    ("v_crystal_ball", Some(true), Some(true), Some(true)),
-    ("j_idol", Some(true), Some(true), Some(true)),
-    ("tag_negative", Some(true), Some(true), None),
-    ("p_celestial_mega_2", Some(true), Some(true), None),
-    ("j_splash", Some(true), Some(true), Some(true)),
-    ("c_empress", Some(true), Some(true), None),
-    ("p_standard_mega_1", Some(true), Some(true), None),
-    ("c_star", Some(true), Some(true), None),
-    ("j_astronomer", Some(true), Some(true), Some(true)),
-    ("j_stone", Some(true), Some(true), Some(true)),
-    ("v_reroll_surplus", Some(true), Some(true), Some(true)),
-    ("bl_final_bell", Some(true), Some(true), None),
-    ("p_spectral_mega_1", Some(true), Some(true), None),
-    ("c_neptune", Some(true), Some(true), None),
-    ("j_odd_todd", Some(true), Some(true), Some(true)),
-    ("j_droll", Some(true), Some(true), Some(true)),
-    ("j_mad", Some(true), Some(true), Some(true)),
-    ("bl_arm", Some(true), Some(true), None),
-    ("j_cartomancer", Some(true), Some(true), Some(true)),
-    ("j_superposition", Some(true), Some(true), Some(true)),
-    ("v_observatory", Some(true), Some(true), Some(true)),
-    ("j_zany", Some(true), Some(true), Some(true)),
-    ("j_mail", Some(true), Some(true), Some(true)),
-    ("j_trousers", Some(true), Some(true), Some(true)),
-    ("p_celestial_jumbo_2", Some(true), Some(true), None),
-    ("bl_flint", Some(true), Some(true), None),
-    ("c_magician", Some(true), Some(true), None),
-    ("v_recyclomancy", Some(true), Some(true), Some(true)),
-    ("v_retcon", Some(true), Some(true), Some(true)),
-    ("j_flower_pot", Some(true), Some(true), Some(true)),
-    ("p_buffoon_jumbo_1", Some(true), Some(true), None),
-    ("b_green", Some(true), Some(true), Some(true)),
-    ("j_business", Some(true), Some(true), Some(true)),
-    ("c_ankh", Some(true), Some(true), None),
-    ("e_negative", Some(true), Some(true), Some(true)),
-    ("j_ring_master", Some(true), Some(true), Some(true)),
-    ("c_high_priestess", Some(true), Some(true), None),
-    ("e_holo", Some(true), Some(true), Some(true)),
-    ("c_fool", Some(true), Some(true), None),
-    ("v_petroglyph", Some(true), Some(true), Some(true)),
-    ("j_half", Some(true), Some(true), Some(true)),
-    ("v_nacho_tong", Some(true), Some(true), Some(true)),
-    ("j_midas_mask", Some(true), Some(true), Some(true)),
-    ("v_wasteful", Some(true), Some(true), Some(true)),
-    ("tag_standard", Some(true), Some(true), None),
-    ("j_drunkard", Some(true), Some(true), Some(true)),
-    ("v_money_tree", Some(true), Some(true), Some(true)),
-    ("c_talisman", Some(true), Some(true), None),
-    ("tag_top_up", Some(true), Some(true), None),
-    ("bl_final_heart", Some(true), Some(true), None),
-    ("j_family", Some(true), Some(true), Some(true)),
-    ("j_brainstorm", Some(true), Some(true), Some(true)),
-    ("j_hit_the_road", Some(true), Some(true), Some(true)),
-    ("j_golden", Some(true), Some(true), Some(true)),
-    ("b_magic", Some(true), Some(true), Some(true)),
-    ("j_shoot_the_moon", Some(true), Some(true), Some(true)),
-    ("tag_coupon", Some(true), Some(true), None),
-    ("j_burnt", Some(true), Some(true), Some(true)),
-    ("j_trio", Some(true), Some(true), Some(true)),
-    ("tag_garbage", Some(true), Some(true), None),
-    ("c_temperance", Some(true), Some(true), None),
-    ("j_banner", Some(true), Some(true), Some(true)),
-    ("v_antimatter", Some(true), Some(true), Some(true)),
-    ("v_liquidation", Some(true), Some(true), Some(true)),
-    ("v_overstock_plus", Some(true), Some(true), Some(true)),
-    ("bl_hook", Some(true), Some(true), None),
-    ("v_seed_money", Some(true), Some(true), Some(true)),
-    ("j_fortune_teller", Some(true), Some(true), Some(true)),
-    ("e_foil", Some(true), Some(true), Some(true)),
-    ("j_chicot", Some(true), Some(true), Some(true)),
-    ("p_arcana_mega_2", Some(true), Some(true), None),
-    ("p_arcana_normal_4", Some(true), Some(true), None),
-    ("j_shortcut", Some(true), Some(true), Some(true)),
-    ("bl_psychic", Some(true), Some(true), None),
-    ("j_scholar", Some(true), Some(true), Some(true)),
-    ("j_mr_bones", Some(true), Some(true), Some(true)),
-    ("b_ghost", Some(true), Some(true), Some(true)),
-    ("c_trance", Some(true), Some(true), None),
-    ("bl_small", Some(true), Some(true), None),
-    ("j_space", Some(true), Some(true), Some(true)),
-    ("j_gift", Some(true), Some(true), Some(true)),
-    ("v_directors_cut", Some(true), Some(true), Some(true)),
-    ("bl_eye", Some(true), Some(true), None),
-    ("j_steel_joker", Some(true), Some(true), Some(true)),
-    ("j_dusk", Some(true), Some(true), Some(true)),
-    ("c_black_hole", Some(true), Some(true), None),
-    ("b_blue", Some(true), Some(true), Some(true)),
-    ("tag_d_six", Some(true), Some(true), None),
-    ("c_venus", Some(true), Some(true), None),
-    ("p_standard_mega_2", Some(true), Some(true), None),
-    ("c_sigil", Some(true), Some(true), Some(true)),
-    ("j_8_ball", Some(true), Some(true), Some(true)),
-    ("j_raised_fist", Some(true), Some(true), Some(true)),
-    ("j_lusty_joker", Some(true), Some(true), Some(true)),
-    ("j_glass", Some(true), Some(true), Some(true)),
-    ("c_hermit", Some(true), Some(true), None),
-    ("j_dna", Some(true), Some(true), Some(true)),
-    ("j_bootstraps", Some(true), Some(true), Some(true)),
-    ("v_planet_merchant", Some(true), Some(true), Some(true)),
-    ("j_misprint", Some(true), Some(true), Some(true)),
-    ("c_tower", Some(true), Some(true), None),
-    ("j_vampire", Some(true), Some(true), Some(true)),
-    ("j_fibonacci", Some(true), Some(true), Some(true)),
-    ("c_uranus", Some(true), Some(true), None),
-    ("c_heirophant", Some(true), Some(true), None),
-    ("c_emperor", Some(true), Some(true), None),
-    ("c_cryptid", Some(true), Some(true), None),
-    ("v_glow_up", Some(true), Some(true), Some(true)),
-    ("j_ice_cream", Some(true), Some(true), Some(true)),
-    ("j_four_fingers", Some(true), Some(true), Some(true)),
-    ("b_erratic", Some(true), Some(true), Some(true)),
-    ("bl_house", Some(true), Some(true), None),
-    ("p_celestial_mega_1", Some(true), Some(true), None),
-    ("v_planet_tycoon", Some(true), Some(true), Some(true)),
-    ("j_lucky_cat", Some(true), Some(true), Some(true)),
-    ("j_stencil", Some(true), Some(true), Some(true)),
-    ("p_standard_jumbo_2", Some(true), Some(true), None),
-    ("j_egg", Some(true), Some(true), Some(true)),
-    ("j_faceless", Some(true), Some(true), Some(true)),
-    ("j_scary_face", Some(true), Some(true), Some(true)),
-    ("tag_holo", Some(true), Some(true), None),
-    ("j_burglar", Some(true), Some(true), Some(true)),
-    ("p_spectral_normal_1", Some(true), Some(true), None),
-    ("j_chaos", Some(true), Some(true), Some(true)),
-    ("j_luchador", Some(true), Some(true), Some(true)),
-    ("p_arcana_jumbo_2", Some(true), Some(true), None),
-    ("bl_needle", Some(true), Some(true), None),
-    ("j_sock_and_buskin", Some(true), Some(true), Some(true)),
-    ("tag_foil", Some(true), Some(true), None),
-    ("p_arcana_mega_1", Some(true), Some(true), None),
-    ("c_incantation", Some(true), Some(true), Some(true)),
-    ("j_pareidolia", Some(true), Some(true), Some(true)),
-    ("j_seance", Some(true), Some(true), Some(true)),
-    ("p_arcana_normal_2", Some(true), Some(true), None),
-    ("j_order", Some(true), Some(true), Some(true)),
-    ("j_rough_gem", Some(true), Some(true), Some(true)),
-    ("j_campfire", Some(true), Some(true), Some(true)),
-    ("bl_big", Some(true), Some(true), None),
-    ("c_pluto", Some(true), Some(true), None),
-    ("j_baron", Some(true), Some(true), Some(true)),
-    ("bl_final_acorn", Some(true), Some(true), None),
-    ("j_hallucination", Some(true), Some(true), Some(true)),
-    ("j_triboulet", Some(true), Some(true), Some(true)),
-    ("j_duo", Some(true), Some(true), Some(true)),
-    ("bl_window", Some(true), Some(true), None),
-    ("j_devious", Some(true), Some(true), Some(true)),
-    ("b_red", Some(true), Some(true), Some(true)),
-    ("bl_water", Some(true), Some(true), None),
-    ("c_grim", Some(true), Some(true), Some(true)),
-    ("j_hologram", Some(true), Some(true), Some(true)),
-    ("p_standard_normal_3", Some(true), Some(true), None),
-    ("j_ramen", Some(true), Some(true), Some(true)),
-    ("tag_economy", Some(true), Some(true), None),
-    ("tag_rare", Some(true), Some(true), None),
-    ("j_obelisk", Some(true), Some(true), Some(true)),
-    ("bl_final_leaf", Some(true), Some(true), None),
-    ("c_death", Some(true), Some(true), None),
-    ("p_standard_normal_4", Some(true), Some(true), None),
-    ("j_hanging_chad", Some(true), Some(true), Some(true)),
-    ("v_clearance_sale", Some(true), Some(true), Some(true)),
-    ("j_baseball", Some(true), Some(true), Some(true)),
-    ("j_credit_card", Some(true), Some(true), Some(true)),
-    ("b_plasma", Some(true), Some(true), Some(true)),
-    ("p_celestial_normal_2", Some(true), Some(true), None),
-    ("j_flash", Some(true), Some(true), Some(true)),
-    ("j_card_sharp", Some(true), Some(true), Some(true)),
-    ("tag_skip", Some(true), Some(true), None),
-    ("bl_serpent", Some(true), Some(true), None),
-    ("j_green_joker", Some(true), Some(true), Some(true)),
-    ("j_red_card", Some(true), Some(true), Some(true)),
-    ("j_mime", Some(true), Some(true), Some(true)),
-    ("j_jolly", Some(true), Some(true), Some(true)),
-    ("b_painted", Some(true), Some(true), Some(true)),
-    ("v_blank", Some(true), Some(true), Some(true)),
-    ("c_saturn", Some(true), Some(true), None),
-    ("j_oops", Some(true), Some(true), Some(true)),
-    ("c_mars", Some(true), Some(true), None),
-    ("j_ride_the_bus", Some(true), Some(true), Some(true)),
-    ("tag_charm", Some(true), Some(true), None),
-    ("c_soul", Some(true), Some(true), None),
-    ("c_immolate", Some(true), Some(true), None),
-    ("c_justice", Some(true), Some(true), None),
-    ("c_ectoplasm", Some(true), Some(true), None),
-    ("bl_mark", Some(true), Some(true), None),
-    ("bl_pillar", Some(true), Some(true), None),
-    ("j_crafty", Some(true), Some(true), Some(true)),
-    ("bl_tooth", Some(true), Some(true), None),
-    ("j_joker", Some(true), Some(true), Some(true)),
-    ("tag_handy", Some(true), Some(true), None),
-    ("j_abstract", Some(true), Some(true), Some(true)),
-    ("j_turtle_bean", Some(true), Some(true), Some(true)),
-    ("p_spectral_normal_2", Some(true), Some(true), None),
-    ("j_constellation", Some(true), Some(true), Some(true)),
-    ("v_omen_globe", Some(true), Some(true), Some(true)),
-    ("c_hex", Some(true), Some(true), None),
-    ("p_standard_normal_1", Some(true), Some(true), None),
-    ("j_smiley", Some(true), Some(true), Some(true)),
-    ("j_gluttenous_joker", Some(true), Some(true), Some(true)),
-    ("p_standard_jumbo_1", Some(true), Some(true), None),
-    ("j_certificate", Some(true), Some(true), Some(true)),
-    ("tag_boss", Some(true), Some(true), None),
-    ("c_earth", Some(true), Some(true), None),
-    ("j_ceremonial", Some(true), Some(true), Some(true)),
-    ("j_juggler", Some(true), Some(true), Some(true)),
-    ("bl_goad", Some(true), Some(true), None),
-    ("tag_voucher", Some(true), Some(true), None),
-    ("j_riff_raff", Some(true), Some(true), Some(true)),
-    ("j_throwback", Some(true), Some(true), Some(true)),
-    ("j_onyx_agate", Some(true), Some(true), Some(true)),
-    ("c_medium", Some(true), Some(true), None),
-    ("j_cavendish", Some(true), Some(true), Some(true)),
-    ("v_hone", Some(true), Some(true), Some(true)),
-    ("c_chariot", Some(true), Some(true), None),
-];
-
-// const j_: i32 = 150;
-// const v_: i32 = 32;
-
-// const c_: i32 = 53; // base counts as one
-
-// const b_: i32 = 16; // challenge counts as one
-
-// const bl_: i32 = 30;
-// const p_: i32 = 32;
-// const m_: i32 = 8;
-// const tag_: i32 = 24;
-// const e_: i32 = 5;
-
-/// A meta save with the default completion
-pub const DEFAULT_META: [(&str, Option<bool>, Option<bool>, Option<bool>);
-    183] = [
-    // name, alerted, discovered, unlocked
-    ("j_business", None, None, Some(true)),
-    ("c_strength", Some(true), None, Some(true)),
-    ("v_grabber", None, None, Some(true)),
-    ("j_fortune_teller", None, None, Some(true)),
-    ("j_blue_joker", None, None, Some(true)),
-    ("j_sly", None, None, Some(true)),
-    ("v_crystal_ball", None, None, Some(true)),
-    ("j_scary_face", None, None, Some(true)),
-    ("p_buffoon_jumbo_1", Some(true), None, None),
-    ("j_seance", None, None, Some(true)),
-    ("p_standard_normal_3", Some(true), None, None),
-    ("j_scholar", None, None, Some(true)),
-    ("j_smiley", None, None, Some(true)),
-    ("j_erosion", None, None, Some(true)),
-    ("v_planet_merchant", None, None, Some(true)),
-    ("j_photograph", None, None, Some(true)),
-    ("j_todo_list", None, None, Some(true)),
-    ("j_to_the_moon", None, None, Some(true)),
-    ("j_square", None, None, Some(true)),
-    ("j_zany", None, None, Some(true)),
-    ("c_grim", Some(true), None, Some(true)),
-    ("j_dna", None, None, Some(true)),
-    ("bl_eye", Some(true), None, None),
-    ("j_pareidolia", None, None, Some(true)),
-    ("j_supernova", None, None, Some(true)),
-    ("j_stone", None, None, Some(true)),
-    ("j_misprint", None, None, Some(true)),
-    ("p_arcana_jumbo_2", Some(true), None, None),
-    ("j_marble", None, None, Some(true)),
-    ("j_constellation", None, None, Some(true)),
-    ("p_arcana_mega_2", Some(true), None, None),
-    ("j_credit_card", None, None, Some(true)),
-    ("j_baseball", None, None, Some(true)),
-    ("p_arcana_mega_1", Some(true), None, None),
-    ("j_wrathful_joker", None, None, Some(true)),
-    ("j_ride_the_bus", None, None, Some(true)),
-    ("j_walkie_talkie", None, None, Some(true)),
-    ("j_runner", None, None, Some(true)),
-    ("j_golden", None, None, Some(true)),
-    ("p_arcana_jumbo_1", Some(true), None, None),
-    ("b_blue", Some(true), None, None),
-    ("p_standard_jumbo_1", Some(true), None, None),
-    ("p_standard_jumbo_2", Some(true), None, None),
-    ("j_raised_fist", None, None, Some(true)),
-    ("j_madness", None, None, Some(true)),
-    ("v_clearance_sale", None, None, Some(true)),
-    ("j_campfire", None, None, Some(true)),
-    ("j_egg", None, None, Some(true)),
-    ("j_sixth_sense", None, None, Some(true)),
-    ("p_celestial_normal_2", Some(true), None, None),
-    ("p_standard_normal_1", Some(true), None, None),
-    ("j_lusty_joker", None, None, Some(true)),
-    ("j_cloud_9", None, None, Some(true)),
-    ("j_gluttenous_joker", None, None, Some(true)),
-    ("j_trousers", None, None, Some(true)),
-    ("p_buffoon_normal_2", Some(true), None, None),
-    ("j_mad", None, None, Some(true)),
-    ("j_cavendish", None, None, Some(true)),
-    ("j_four_fingers", None, None, Some(true)),
-    ("j_fibonacci", None, None, Some(true)),
-    ("c_incantation", Some(true), None, Some(true)),
-    ("b_nebula", Some(true), None, None),
-    ("b_anaglyph", Some(true), None, None),
-    ("j_rocket", None, None, Some(true)),
-    ("j_bull", None, None, Some(true)),
-    ("j_steel_joker", None, None, Some(true)),
-    ("b_plasma", Some(true), None, None),
-    ("p_spectral_normal_1", Some(true), None, None),
-    ("b_yellow", Some(true), None, None),
-    ("j_droll", None, None, Some(true)),
-    ("v_hieroglyph", None, None, Some(true)),
-    ("j_loyalty_card", None, None, Some(true)),
-    ("j_mime", None, None, Some(true)),
-    ("p_celestial_jumbo_2", Some(true), None, None),
-    ("j_8_ball", None, None, Some(true)),
-    ("e_foil", Some(true), None, Some(true)),
-    ("j_diet_cola", None, None, Some(true)),
-    ("p_spectral_normal_2", Some(true), None, None),
-    ("c_ouija", Some(true), None, Some(true)),
-    ("v_seed_money", None, None, Some(true)),
-    ("j_hack", None, None, Some(true)),
-    ("j_ice_cream", None, None, Some(true)),
-    ("j_selzer", None, None, Some(true)),
-    ("p_buffoon_normal_1", Some(true), None, None),
-    ("b_zodiac", Some(true), None, None),
-    ("p_arcana_normal_2", Some(true), None, None),
-    ("j_red_card", None, None, Some(true)),
-    ("b_erratic", Some(true), None, None),
-    ("j_burglar", None, None, Some(true)),
-    ("j_vampire", None, None, Some(true)),
-    ("j_midas_mask", None, None, Some(true)),
-    ("j_drunkard", None, None, Some(true)),
-    ("p_standard_mega_1", Some(true), None, None),
-    ("p_buffoon_mega_1", Some(true), None, None),
-    ("c_familiar", Some(true), None, Some(true)),
-    ("j_shortcut", None, None, Some(true)),
-    ("j_delayed_grat", None, None, Some(true)),
-    ("b_abandoned", Some(true), None, None),
-    ("j_dusk", None, None, Some(true)),
-    ("j_odd_todd", None, None, Some(true)),
-    ("j_splash", None, None, Some(true)),
-    ("j_hiker", None, None, Some(true)),
-    ("v_wasteful", None, None, Some(true)),
-    ("p_celestial_normal_4", Some(true), None, None),
-    ("p_standard_normal_4", Some(true), None, None),
-    ("j_greedy_joker", None, None, Some(true)),
-    ("j_hologram", None, None, Some(true)),
-    ("j_joker", Some(true), Some(true), Some(true)),
-    ("j_space", None, None, Some(true)),
-    ("p_standard_normal_2", Some(true), None, None),
-    ("j_half", None, None, Some(true)),
-    ("j_reserved_parking", None, None, Some(true)),
-    ("p_celestial_mega_2", Some(true), None, None),
-    ("b_checkered", Some(true), None, None),
-    ("j_juggler", None, None, Some(true)),
-    ("j_superposition", None, None, Some(true)),
-    ("j_obelisk", None, None, Some(true)),
-    ("j_devious", None, None, Some(true)),
-    ("b_painted", Some(true), None, None),
-    ("p_celestial_normal_3", Some(true), None, None),
-    ("v_blank", None, None, Some(true)),
-    ("j_popcorn", None, None, Some(true)),
-    ("p_arcana_normal_1", Some(true), None, None),
-    ("j_riff_raff", None, None, Some(true)),
-    ("j_wily", None, None, Some(true)),
-    ("v_telescope", None, None, Some(true)),
-    ("j_gros_michel", None, None, Some(true)),
-    ("j_flash", None, None, Some(true)),
-    ("j_hallucination", None, None, Some(true)),
-    ("j_mail", None, None, Some(true)),
-    ("j_vagabond", None, None, Some(true)),
-    ("j_stencil", None, None, Some(true)),
-    ("p_spectral_jumbo_1", Some(true), None, None),
-    ("c_sigil", Some(true), None, Some(true)),
-    ("p_spectral_mega_1", Some(true), None, None),
-    ("e_polychrome", Some(true), None, Some(true)),
-    ("b_magic", Some(true), None, None),
-    ("b_challenge", Some(true), None, Some(true)),
-    ("j_trading", None, None, Some(true)),
-    ("v_tarot_merchant", None, None, Some(true)),
-    ("b_red", Some(true), Some(true), Some(true)),
-    ("b_green", Some(true), None, None),
-    ("j_castle", None, None, Some(true)),
-    ("j_banner", None, None, Some(true)),
-    ("v_directors_cut", None, None, Some(true)),
-    ("j_abstract", None, None, Some(true)),
-    ("p_celestial_jumbo_1", Some(true), None, None),
-    ("j_jolly", None, None, Some(true)),
-    ("v_hone", None, None, Some(true)),
-    ("j_baron", None, None, Some(true)),
-    ("j_clever", None, None, Some(true)),
-    ("j_mystic_summit", None, None, Some(true)),
-    ("e_negative", Some(true), None, Some(true)),
-    ("j_lucky_cat", None, None, Some(true)),
-    ("j_crafty", None, None, Some(true)),
-    ("j_green_joker", None, None, Some(true)),
-    ("j_faceless", None, None, Some(true)),
-    ("j_crazy", None, None, Some(true)),
-    ("j_turtle_bean", None, None, Some(true)),
-    ("p_celestial_mega_1", Some(true), None, None),
-    ("e_base", None, None, Some(true)),
-    ("j_ramen", None, None, Some(true)),
-    ("v_magic_trick", None, None, Some(true)),
-    ("j_even_steven", None, None, Some(true)),
-    ("b_black", Some(true), None, None),
-    ("j_card_sharp", None, None, Some(true)),
-    ("p_standard_mega_2", Some(true), None, None),
-    ("j_blackboard", None, None, Some(true)),
-    ("e_holo", Some(true), None, Some(true)),
-    ("j_luchador", None, None, Some(true)),
-    ("j_ceremonial", None, None, Some(true)),
-    ("v_paint_brush", None, None, Some(true)),
-    ("bl_wheel", Some(true), None, None),
-    ("j_chaos", None, None, Some(true)),
-    ("j_gift", None, None, Some(true)),
-    ("p_arcana_normal_4", Some(true), None, None),
-    ("v_reroll_surplus", None, None, Some(true)),
-    ("j_ancient", None, None, Some(true)),
-    ("v_observatory", Some(true), None, None),
-    ("p_celestial_normal_1", Some(true), None, None),
-    ("v_overstock_norm", None, None, Some(true)),
-    ("p_arcana_normal_3", Some(true), None, None),
-    ("b_ghost", Some(true), None, None),
-];
@@ -11,6 +11,12 @@ pub struct DevTest {
     pub output: String,
 }
 #[cfg(feature = "dev")]
+impl Default for DevTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+#[cfg(feature = "dev")]
 impl DevTest {
     /// Creates a new DevTest
     pub fn new() -> Self {
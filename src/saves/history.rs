@@ -0,0 +1,60 @@
+/// The most edits kept, older ones can no longer be undone
+pub const HISTORY_LIMIT: usize = 200;
+
+/// A list of edits that can be undone and redone
+/// Each edit is a command that knows how to undo and redo itself, so the
+/// history works the same for any kind of save
+#[derive(Debug, Clone)]
+pub struct History<E> {
+    /// The edits that can be undone, oldest first
+    undo: Vec<E>,
+    /// The edits that were undone and can be redone, most recent last
+    redo: Vec<E>,
+}
+
+impl<E> History<E> {
+    /// Create a new History with no edits
+    pub fn new() -> Self {
+        History { undo: Vec::new(), redo: Vec::new() }
+    }
+
+    /// Adds an edit that was just made
+    /// The edits that were undone can no longer be redone after this
+    pub fn push(&mut self, edit: E) {
+        self.redo.clear();
+        self.undo.push(edit);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Moves the last edit to the redo list and returns it to be undone
+    pub fn undo(&mut self) -> Option<&E> {
+        let edit = self.undo.pop()?;
+        self.redo.push(edit);
+        self.redo.last()
+    }
+
+    /// Moves the last undone edit back and returns it to be redone
+    pub fn redo(&mut self) -> Option<&E> {
+        let edit = self.redo.pop()?;
+        self.undo.push(edit);
+        self.undo.last()
+    }
+
+    /// Returns whether there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns whether there is an edit to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+impl<E> Default for History<E> {
+    fn default() -> Self {
+        History::new()
+    }
+}
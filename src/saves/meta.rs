@@ -1,14 +1,24 @@
-use crate::lua::LuaContext;
-use crate::saves::defaults::{ALL_META, DEFAULT_META};
+use crate::enums::{MergeStrategy, MetaFlag, SaveError, SaveKind};
+use crate::lua::{self, SaveKey, SaveTable, SaveValue};
+use crate::saves::History;
+mod bulk;
+mod export;
 mod filters;
+mod itemkey;
+mod metaedit;
 mod metaitem;
+mod mods;
+mod stats;
+mod version;
 
 pub use filters::Filters;
+pub use itemkey::{ItemKey, KNOWN_MODS, is_known_mod, mod_name};
+pub use metaedit::{ItemChange, MetaEdit};
 pub use metaitem::MetaItem;
+pub use stats::{CategoryStats, FlagCount};
 
 use fuzzy_matcher::skim::SkimMatcherV2;
-use std::collections::{HashMap, HashSet};
-use std::io::Write;
+use std::collections::HashMap;
 
 /// Meta struct for the meta save file
 /// This struct is used to store and modify the state of the items in the meta file
@@ -20,6 +30,14 @@ pub struct Meta {
     pub filters: Filters,
     /// The matcher for the fuzzy search
     pub matcher: SkimMatcherV2,
+    /// The edits that can be undone and redone
+    history: History<MetaEdit>,
+    /// The version of the game the meta is from, if it is known
+    version: Option<String>,
+    /// The VERSION the meta file itself has, which is written back when it
+    /// is saved
+    /// A version found some other way, see find_version, is never written
+    file_version: Option<String>,
 }
 
 impl Meta {
@@ -29,143 +47,85 @@ impl Meta {
             items: HashMap::new(),
             filters: Filters::new(),
             matcher: SkimMatcherV2::default(),
+            history: History::new(),
+            version: None,
+            file_version: None,
         }
     }
     /// Convert the struct into the raw data that is used in the save file
-    pub fn to_lua_data(
-        &self,
-        lua: &LuaContext,
-    ) -> Result<Vec<u8>, mlua::Error> {
-        let table = lua.lua.create_table()?;
-        let alerted_table = lua.lua.create_table()?;
-        let discovered_table = lua.lua.create_table()?;
-        let unlocked_table = lua.lua.create_table()?;
-
-        for (name, item) in self.items.iter() {
-            alerted_table.set(name.clone(), item.alerted)?;
-            discovered_table.set(name.clone(), item.discovered)?;
-            unlocked_table.set(name.clone(), item.unlocked)?;
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        let mut alerted_table = SaveTable::new();
+        let mut discovered_table = SaveTable::new();
+        let mut unlocked_table = SaveTable::new();
+
+        // Sorted so saving the same meta twice gives the same file
+        let mut names: Vec<&String> = self.items.keys().collect();
+        names.sort();
+        for name in names {
+            let item = &self.items[name];
+            alerted_table.insert(name.as_str(), SaveValue::Bool(item.alerted));
+            discovered_table
+                .insert(name.as_str(), SaveValue::Bool(item.discovered));
+            unlocked_table
+                .insert(name.as_str(), SaveValue::Bool(item.unlocked));
         }
 
-        table.set("alerted", alerted_table)?;
-        table.set("discovered", discovered_table)?;
-        table.set("unlocked", unlocked_table)?;
-
-        // This is taken directly from the game engine of balatro, including the
-        // license for the file (Not that it really matters since this is MIT licensed)
-        let compress_func: mlua::Function= lua.lua.load(
-            r#"
-            --[[
-MIT License
-Copyright (c) 2017 Robert Herlihy
-Permission is hereby granted, free of charge, to any person obtaining a copy
-of this software and associated documentation files (the "Software"), to deal
-in the Software without restriction, including without limitation the rights
-to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
-copies of the Software, and to permit persons to whom the Software is
-furnished to do so, subject to the following conditions:
-The above copyright notice and this permission notice shall be included in all
-copies or substantial portions of the Software.
-THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
-IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
-FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
-AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
-LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
-OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
-SOFTWARE.
-]]
-
---I modified this A LOT. Needed to make it quicker if it is being saved to file every few seconds during a game
-function STR_PACK(data, recursive)
-	local ret_str = (recursive and "" or "return ").."{"
-
-      for i, v in pairs(data) do
-		local type_i, type_v = type(i), type(v)
-        assert((type_i ~= "table"), "Data table cannot have an table as a key reference")
-        if type_i == "string" then
-			i = '['..string.format("%q",i)..']'
-        else
-          	i = "["..i.."]"
-        end
-        if type_v == "table" then
-			if v.is and v:is(Object) then
-				v = [["]].."MANUAL_REPLACE"..[["]]
-			else
-				v = STR_PACK(v, true)
-			end
-        else
-          if type_v == "string" then v = string.format("%q", v) end
-		  if type_v == "boolean" then v = v and "true" or "false" end
-        end
-		ret_str = ret_str..i.."="..v..","
-      end
-
-	  return ret_str.."}"
-
-end
-
-return STR_PACK
-"#
-        ).eval()?;
+        let mut table = SaveTable::new();
+        table.insert("alerted", SaveValue::Table(alerted_table));
+        table.insert("discovered", SaveValue::Table(discovered_table));
+        table.insert("unlocked", SaveValue::Table(unlocked_table));
+        if let Some(version) = &self.file_version {
+            table.insert("VERSION", SaveValue::String(version.clone()));
+        }
 
-        let compressed = compress_func.call::<String>(table)?;
-        let mut encoder = flate2::write::DeflateEncoder::new(
-            Vec::new(),
-            flate2::Compression::default(),
-        );
-        encoder.write_all(compressed.as_bytes())?;
-        Ok(encoder.finish()?)
+        lua::to_save_data(&SaveValue::Table(table))
     }
 
     /// Convert the raw data from the save file into a Meta struct
-    pub fn from_lua_table(
-        lua: LuaContext,
-        data: Vec<u8>,
-    ) -> Result<Self, mlua::Error> {
-        let table = lua.data_as_table(data, "map_table")?;
-        let mut meta = Meta::from_defaults();
-        let mut names: HashSet<String> = HashSet::new();
-
-        // Access the subtables
-        let alerted_table = lua.access_subtable(&table, "alerted")?;
-        let discovered_table = lua.access_subtable(&table, "discovered")?;
-        let unlocked_table = lua.access_subtable(&table, "unlocked")?;
+    /// The file is parsed natively, so no lua is run while loading
+    pub fn from_lua_table(data: Vec<u8>) -> Result<Self, SaveError> {
+        Meta::from_save_value(lua::parse_save_data(&data)?)
+    }
 
-        for pair in alerted_table.pairs::<String, bool>() {
-            let (name, _) = pair?;
-            names.insert(name.clone());
-        }
-        for pair in discovered_table.pairs::<String, bool>() {
-            let (name, _) = pair?;
-            names.insert(name.clone());
-        }
+    /// Convert a decoded meta file into a Meta struct
+    pub fn from_save_value(value: SaveValue) -> Result<Self, SaveError> {
+        SaveError::check_kind(&value, SaveKind::Meta)?;
+        let table = match value.as_table() {
+            Some(table) => table,
+            None => {
+                return Err(SaveError::WrongFileType {
+                    expected: SaveKind::Meta,
+                    found: SaveKind::Unknown,
+                });
+            }
+        };
+        // Meta files do not usually have a version, but one that does is
+        // checked against the items of that version
+        let version = table
+            .get("VERSION")
+            .and_then(SaveValue::as_str)
+            .map(str::to_string);
+        let mut meta = Meta::defaults_for(version.clone());
+        meta.file_version = version;
+        let mut flags: HashMap<&str, [Option<bool>; 3]> = HashMap::new();
 
-        for pair in unlocked_table.pairs::<String, bool>() {
-            let (name, _) = pair?;
-            names.insert(name.clone());
+        // Access the subtables
+        let alerted_table = table.access_subtable("alerted")?;
+        let discovered_table = table.access_subtable("discovered")?;
+        let unlocked_table = table.access_subtable("unlocked")?;
+
+        // Each subtable is walked once, collecting the alerted, discovered
+        // and unlocked values of every name in that order
+        let subtables = [alerted_table, discovered_table, unlocked_table];
+        for (i, subtable) in subtables.iter().enumerate() {
+            for (key, value) in subtable.iter() {
+                if let SaveKey::Str(name) = key {
+                    flags.entry(name).or_default()[i] = Some(value.is_truthy());
+                }
+            }
         }
 
-        for name in names.iter() {
-            let alerted: Option<bool> =
-                if alerted_table.contains_key(name.clone())? {
-                    Some(alerted_table.get(name.clone())?)
-                } else {
-                    None
-                };
-
-            let discovered: Option<bool> =
-                if discovered_table.contains_key(name.clone())? {
-                    Some(discovered_table.get(name.clone())?)
-                } else {
-                    None
-                };
-
-            let unlocked: Option<bool> =
-                if unlocked_table.contains_key(name.clone())? {
-                    Some(unlocked_table.get(name.clone())?)
-                } else {
-                    None
-                };
+        for (name, [alerted, discovered, unlocked]) in flags {
             meta.update_item(name, alerted, discovered, unlocked);
         }
 
@@ -173,49 +133,34 @@ return STR_PACK
         Ok(meta)
     }
 
-    /// Add the default items to the meta struct
+    /// Add the items a new profile has to the meta struct, if it is missing
+    /// them
     fn add_missing_defaults(&mut self) {
-        for (name, alerted, discovered, unlocked) in DEFAULT_META.iter() {
-            if !self.items.contains_key(*name) {
-                self.add_item(
-                    name.to_string(),
-                    *alerted,
-                    *discovered,
-                    *unlocked,
-                );
+        for entry in self.registry().entries() {
+            if entry.in_new_profile() && !self.items.contains_key(&entry.key) {
+                self.items.insert(entry.key.clone(), entry.default_item());
             }
         }
     }
-    /// Create a new Meta struct with the default values
+    /// Create a new Meta struct with every item in the registry, as a new
+    /// profile has it
     pub fn from_defaults() -> Self {
+        Meta::defaults_for(None)
+    }
+    /// Create a new Meta struct with every item of a game version, as a new
+    /// profile has it, see Registries::for_version
+    pub fn defaults_for(version: Option<String>) -> Self {
         let mut meta = Meta::new();
-        for (name, alerted, discovered, unlocked) in ALL_META.iter() {
-            let mut alerted = *alerted;
-            let mut discovered = *discovered;
-            let mut unlocked = *unlocked;
-
-            if alerted.is_some() {
-                alerted = Some(false);
-            }
-            if discovered.is_some() {
-                discovered = Some(false);
-            }
-            if unlocked.is_some() {
-                unlocked = Some(false);
-            }
-
-            meta.add_item(name.to_string(), alerted, discovered, unlocked);
+        meta.version = version;
+        for entry in meta.registry().entries() {
+            meta.items.insert(entry.key.clone(), entry.default_item());
         }
-
-        for (name, alerted, discovered, unlocked) in DEFAULT_META.iter() {
-            meta.update_item(*name, *alerted, *discovered, *unlocked);
-        }
-
         meta
     }
 
     /// Update the item in the meta struct
-    /// If the item does not exist, it will be added
+    /// If the item does not exist, it will be added as a modded item, along
+    /// with the mod its key says it is from
     fn update_item(
         &mut self,
         name: &str,
@@ -243,22 +188,12 @@ return STR_PACK
             if unlocked.is_none() {
                 unlocked = Some(false);
             }
-            let item = MetaItem::new(alerted, discovered, unlocked);
+            let item = MetaItem::new(alerted, discovered, unlocked)
+                .with_mod(ItemKey::parse(name, self.registry()).mod_prefix);
             self.items.insert(name.to_string(), item);
         }
     }
 
-    // / Add an item to the meta struct
-    fn add_item(
-        &mut self,
-        name: String,
-        alerted: Option<bool>,
-        discovered: Option<bool>,
-        unlocked: Option<bool>,
-    ) {
-        let item = MetaItem::new(alerted, discovered, unlocked);
-        self.items.insert(name, item);
-    }
     /// Get a mutable reference to the item in the meta struct
     pub fn get_item(&mut self, name: &str) -> Option<&mut MetaItem> {
         self.items.get_mut(name)
@@ -278,23 +213,155 @@ return STR_PACK
 
     /// Unlock all items of a certain type
     pub fn unlock_all_type(&mut self, type_: &str) {
+        let registry = self.registry();
+        for (name, value) in self.items.iter_mut() {
+            if name.starts_with(type_) {
+                match registry.get(name) {
+                    Some(entry) => {
+                        value.alerted = entry.can_have(MetaFlag::Alerted);
+                        value.discovered = entry.can_have(MetaFlag::Discovered);
+                        value.unlocked = entry.can_have(MetaFlag::Unlocked);
+                    }
+                    None => {
+                        value.alerted = true;
+                        value.discovered = true;
+                        value.unlocked = true;
+                    }
+                }
+            }
+        }
+    }
+    /// Lock all items of a certain type
+    /// Every flag is cleared, so the items are as they are in a new profile
+    /// that has never seen them
+    pub fn lock_all_type(&mut self, type_: &str) {
         for (name, value) in self.items.iter_mut() {
             if name.starts_with(type_) {
-                let default_value =
-                    ALL_META.iter().find(|(name, _, _, _)| name == name);
+                value.alerted = false;
+                value.discovered = false;
+                value.unlocked = false;
+            }
+        }
+    }
+    /// Merges another meta into this one as a single undo step
+    /// Returns the changes that were made, see merge_changes
+    pub fn merge(&mut self, other: &Meta, strategy: MergeStrategy) -> MetaEdit {
+        let changes = self.merge_changes(other, strategy);
+        self.apply(&changes);
+        changes
+    }
 
-                if default_value.is_none() {
-                    value.alerted = true;
-                    value.discovered = true;
-                    value.unlocked = true;
-                } else {
-                    let default_value = default_value.unwrap();
-                    value.alerted = default_value.1.unwrap_or(false);
-                    value.discovered = default_value.2.unwrap_or(false);
-                    value.unlocked = default_value.3.unwrap_or(false);
+    /// Works out what merging another meta into this one would change,
+    /// without changing anything
+    ///
+    /// Items in only one of the files, like modded items, are kept. With an
+    /// intersection their flags are cleared, as the other file never set them.
+    pub fn merge_changes(
+        &self,
+        other: &Meta,
+        strategy: MergeStrategy,
+    ) -> MetaEdit {
+        let mut merged = self.items.clone();
+        for (name, mine) in merged.iter_mut() {
+            let theirs = other.items.get(name);
+            *mine = match (strategy, theirs) {
+                (MergeStrategy::Union, Some(theirs)) => {
+                    mine.combine(theirs, |a, b| a || b)
+                }
+                (MergeStrategy::Intersection, Some(theirs)) => {
+                    mine.combine(theirs, |a, b| a && b)
                 }
+                (MergeStrategy::Intersection, None) => mine.cleared(),
+                (MergeStrategy::PreferRight, Some(theirs)) => theirs.clone(),
+                _ => mine.clone(),
+            };
+        }
+        for (name, theirs) in other.items.iter() {
+            if !merged.contains_key(name) {
+                let item = match strategy {
+                    MergeStrategy::Intersection => theirs.cleared(),
+                    _ => theirs.clone(),
+                };
+                merged.insert(name.clone(), item);
+            }
+        }
+        metaedit::diff_items(&self.items, &merged)
+    }
+
+    /// Works out what an edit would change, without changing anything
+    /// The edit is run on a copy of the items
+    pub fn preview(&self, edit: impl FnOnce(&mut Meta)) -> MetaEdit {
+        let mut copy = Meta::new();
+        copy.items = self.items.clone();
+        copy.version = self.version.clone();
+        edit(&mut copy);
+        self.diff(&copy)
+    }
+
+    /// Makes a list of changes as a single undo step
+    pub fn apply(&mut self, changes: &MetaEdit) {
+        self.record(|meta| {
+            for change in changes {
+                set_item(&mut meta.items, &change.name, change.after.clone());
             }
+        });
+    }
+
+    /// Runs an edit as a single step that can be undone
+    /// Everything the edit changes is undone together, so a bulk operation
+    /// is undone with one undo
+    pub fn record<R>(&mut self, edit: impl FnOnce(&mut Meta) -> R) -> R {
+        let before = self.items.clone();
+        let result = edit(self);
+
+        let changes = metaedit::diff_items(&before, &self.items);
+        if !changes.is_empty() {
+            self.history.push(changes);
         }
+        result
+    }
+
+    /// Compares this meta to another, item by item
+    /// The changes are what turns this meta into the other one
+    pub fn diff(&self, other: &Meta) -> MetaEdit {
+        metaedit::diff_items(&self.items, &other.items)
+    }
+
+    /// Undoes the last edit, returning false if there was none
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.history.undo() else {
+            return false;
+        };
+        for change in edit {
+            set_item(&mut self.items, &change.name, change.before.clone());
+        }
+        true
+    }
+
+    /// Redoes the last undone edit, returning false if there was none
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.history.redo() else {
+            return false;
+        };
+        for change in edit {
+            set_item(&mut self.items, &change.name, change.after.clone());
+        }
+        true
+    }
+
+    /// Returns whether there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Returns whether there is an edit to redo
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Iterate over all of the items in the meta struct, in no order
+    pub fn items(&self) -> impl Iterator<Item = (&String, &MetaItem)> {
+        self.items.iter()
     }
     /// Get a list of all the voucher names in the meta struct
     pub fn get_voucher_names(&self) -> Vec<String> {
@@ -362,3 +429,19 @@ return STR_PACK
         all
     }
 }
+
+/// Puts an item back as it was, None removing it
+fn set_item(
+    items: &mut HashMap<String, MetaItem>,
+    name: &str,
+    item: Option<MetaItem>,
+) {
+    match item {
+        Some(item) => {
+            items.insert(name.to_string(), item);
+        }
+        None => {
+            items.remove(name);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::Meta;
+use crate::enums::{ItemCategory, MetaFlag};
+
+impl Meta {
+    /// Sets one flag on every item of a category
+    /// Items that can not have the flag are left alone
+    pub fn set_flag_category(
+        &mut self,
+        category: ItemCategory,
+        flag: MetaFlag,
+        value: bool,
+    ) {
+        for (name, item) in self.items.iter_mut() {
+            if ItemCategory::of(name) == Some(category) {
+                item.set_flag(flag, value);
+            }
+        }
+    }
+
+    /// Unlocks every item of a category, see unlock_all_type
+    pub fn unlock_category(&mut self, category: ItemCategory) {
+        self.unlock_all_type(category.prefix());
+    }
+
+    /// Locks every item of a category, see lock_all_type
+    pub fn lock_category(&mut self, category: ItemCategory) {
+        self.lock_all_type(category.prefix());
+    }
+
+    /// Puts every item of a category back to how a new profile has it
+    /// Items that are not in the registry, like most modded ones, are locked
+    pub fn reset_category(&mut self, category: ItemCategory) {
+        let registry = self.registry();
+        for (name, item) in self.items.iter_mut() {
+            if ItemCategory::of(name) != Some(category) {
+                continue;
+            }
+            let entry = registry.get(name);
+            let default = |flag| {
+                entry.and_then(|e| e.default_flag(flag)).unwrap_or(false)
+            };
+            item.alerted = default(MetaFlag::Alerted);
+            item.discovered = default(MetaFlag::Discovered);
+            item.unlocked = default(MetaFlag::Unlocked);
+        }
+    }
+
+    /// Discovers every item of a category without unlocking any
+    pub fn discover_category(&mut self, category: ItemCategory) {
+        self.set_flag_category(category, MetaFlag::Discovered, true);
+    }
+
+    /// Clears the alerted badge of every item of a category
+    pub fn clear_alerts(&mut self, category: ItemCategory) {
+        self.set_flag_category(category, MetaFlag::Alerted, false);
+    }
+}
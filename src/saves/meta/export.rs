@@ -0,0 +1,155 @@
+use super::Meta;
+use crate::enums::{ExportFormat, MetaFlag, SaveError};
+use crate::registry::Registry;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// An item as it is written to an exported file, one row per item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ItemRow {
+    name: String,
+    alerted: bool,
+    discovered: bool,
+    unlocked: bool,
+}
+
+/// The layout of JSON and TOML exports, which need a table at the top
+#[derive(Debug, Serialize, Deserialize)]
+struct MetaExport {
+    items: Vec<ItemRow>,
+}
+
+impl Meta {
+    /// Writes the items to text in an export format, sorted by name
+    pub fn export(&self, format: ExportFormat) -> Result<String, SaveError> {
+        let mut items: Vec<ItemRow> = self
+            .items
+            .iter()
+            .map(|(name, item)| ItemRow {
+                name: name.clone(),
+                alerted: item.alerted,
+                discovered: item.discovered,
+                unlocked: item.unlocked,
+            })
+            .collect();
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let serialize =
+            |err: &dyn std::fmt::Display| SaveError::Serialize(err.to_string());
+        match format {
+            ExportFormat::Json => {
+                serde_json::to_string_pretty(&MetaExport { items })
+                    .map_err(|err| serialize(&err))
+            }
+            ExportFormat::Toml => toml::to_string(&MetaExport { items })
+                .map_err(|err| serialize(&err)),
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                for item in items {
+                    writer.serialize(item).map_err(|err| serialize(&err))?;
+                }
+                let data = writer
+                    .into_inner()
+                    .map_err(|err| serialize(&err.error()))?;
+                String::from_utf8(data).map_err(|err| serialize(&err))
+            }
+        }
+    }
+
+    /// Reads a meta from text in an export format
+    ///
+    /// The items are checked against the known items first, and nothing is
+    /// read if any fail. Unknown items, like modded ones, are only allowed
+    /// with allow_unknown. Items missing from the text get their defaults.
+    pub fn import(
+        text: &str,
+        format: ExportFormat,
+        allow_unknown: bool,
+    ) -> Result<Meta, SaveError> {
+        Meta::import_for(text, format, allow_unknown, None)
+    }
+    /// Reads a meta from text in an export format, checked against the
+    /// items of a game version, see Meta::defaults_for
+    pub fn import_for(
+        text: &str,
+        format: ExportFormat,
+        allow_unknown: bool,
+        version: Option<String>,
+    ) -> Result<Meta, SaveError> {
+        let import =
+            |err: &dyn std::fmt::Display| SaveError::Import(err.to_string());
+        let items = match format {
+            ExportFormat::Json => {
+                serde_json::from_str::<MetaExport>(text)
+                    .map_err(|err| import(&err))?
+                    .items
+            }
+            ExportFormat::Toml => {
+                toml::from_str::<MetaExport>(text)
+                    .map_err(|err| import(&err))?
+                    .items
+            }
+            ExportFormat::Csv => csv::Reader::from_reader(text.as_bytes())
+                .deserialize()
+                .collect::<Result<Vec<ItemRow>, csv::Error>>()
+                .map_err(|err| import(&err))?,
+        };
+        let mut meta = Meta::defaults_for(version);
+        validate(&items, meta.registry(), allow_unknown)?;
+
+        for row in items {
+            match meta.get_item(&row.name) {
+                Some(item) => {
+                    item.alerted = row.alerted;
+                    item.discovered = row.discovered;
+                    item.unlocked = row.unlocked;
+                }
+                None => meta.update_item(
+                    &row.name,
+                    Some(row.alerted),
+                    Some(row.discovered),
+                    Some(row.unlocked),
+                ),
+            }
+        }
+        Ok(meta)
+    }
+}
+
+/// Checks imported items against the known items
+/// Every problem is reported at once, so a file can be fixed in one go
+fn validate(
+    items: &[ItemRow],
+    registry: &Registry,
+    allow_unknown: bool,
+) -> Result<(), SaveError> {
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for row in items {
+        if !seen.insert(row.name.as_str()) {
+            problems.push(format!("{} is listed more than once", row.name));
+        }
+        let Some(entry) = registry.get(&row.name) else {
+            if !allow_unknown {
+                problems.push(format!("{} is not a known item", row.name));
+            }
+            continue;
+        };
+        let flags = [
+            (MetaFlag::Alerted, row.alerted),
+            (MetaFlag::Discovered, row.discovered),
+            (MetaFlag::Unlocked, row.unlocked),
+        ];
+        for (flag, value) in flags {
+            if value && !entry.can_have(flag) {
+                problems.push(format!("{} can not be {}", row.name, flag));
+            }
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(SaveError::Import(problems.join(", ")))
+    }
+}
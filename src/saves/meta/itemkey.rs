@@ -0,0 +1,90 @@
+use crate::enums::ItemCategory;
+use crate::registry::Registry;
+
+/// The mods whose keys are known, by prefix, and the names they are shown as
+pub const KNOWN_MODS: [(&str, &str); 3] =
+    [("cry", "Cryptid"), ("mp", "Multiplayer"), ("mtg", "Magic: The Jokering")];
+
+/// The key of an item, split into its parts
+///
+/// Keys are the type prefix, then the mod prefix for modded items, then the
+/// name, like j_cry_happyhouse. Vanilla keys have no mod prefix, even when
+/// their name has an underscore in it, like j_gros_michel. Only prefixes of
+/// mods that are known are taken as one, so j_foo_bar is an unknown key
+/// rather than one from a mod called foo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemKey<'a> {
+    /// The type of the item, like j for jokers
+    pub type_prefix: &'a str,
+    /// The mod the item is from, if it is modded
+    pub mod_prefix: Option<&'a str>,
+    /// The rest of the key, like happyhouse
+    pub name: &'a str,
+    /// Whether the key is in the registry or from a known mod
+    pub known: bool,
+}
+
+impl<'a> ItemKey<'a> {
+    /// Splits a key into its parts
+    /// Keys in the registry are from the mod their entry gives, for any other
+    /// key the part after the type prefix is taken as the mod prefix if it
+    /// is in KNOWN_MODS or the registry has items from it
+    /// The registry should be the one of the meta the key is from, see
+    /// Meta::registry
+    pub fn parse(key: &'a str, registry: &'a Registry) -> Self {
+        let Some((type_prefix, rest)) = key.split_once('_') else {
+            return ItemKey {
+                type_prefix: "",
+                mod_prefix: None,
+                name: key,
+                known: registry.contains(key),
+            };
+        };
+        let entry = registry.get(key);
+        let (mod_prefix, name) = match entry {
+            Some(entry) => match entry.mod_prefix.as_deref() {
+                Some(mod_prefix) => {
+                    let name = rest
+                        .strip_prefix(mod_prefix)
+                        .and_then(|name| name.strip_prefix('_'))
+                        .unwrap_or(rest);
+                    (Some(mod_prefix), name)
+                }
+                None => (None, rest),
+            },
+            None => match rest.split_once('_') {
+                Some((mod_prefix, name))
+                    if !name.is_empty()
+                        && (is_known_mod(mod_prefix)
+                            || registry.knows_mod(mod_prefix)) =>
+                {
+                    (Some(mod_prefix), name)
+                }
+                _ => (None, rest),
+            },
+        };
+        let known = entry.is_some() || mod_prefix.is_some();
+        ItemKey { type_prefix, mod_prefix, name, known }
+    }
+
+    /// Works out the category of the item from its type prefix
+    pub fn category(&self) -> Option<ItemCategory> {
+        ItemCategory::ALL.into_iter().find(|category| {
+            category.prefix().strip_suffix('_') == Some(self.type_prefix)
+        })
+    }
+}
+
+/// Checks if a mod is one of the KNOWN_MODS
+pub fn is_known_mod(mod_prefix: &str) -> bool {
+    KNOWN_MODS.iter().any(|(prefix, _)| *prefix == mod_prefix)
+}
+
+/// Returns the name a mod is shown as, which is its prefix for mods that
+/// are not in KNOWN_MODS
+pub fn mod_name(mod_prefix: &str) -> &str {
+    KNOWN_MODS
+        .iter()
+        .find(|(prefix, _)| *prefix == mod_prefix)
+        .map_or(mod_prefix, |(_, name)| name)
+}
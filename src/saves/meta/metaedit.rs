@@ -0,0 +1,61 @@
+use super::MetaItem;
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A change to one item in a meta file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemChange {
+    /// The key of the item
+    pub name: String,
+    /// The item before the change, None if it was added
+    pub before: Option<MetaItem>,
+    /// The item after the change, None if it was removed
+    pub after: Option<MetaItem>,
+}
+
+/// Every item one edit changed, which is undone as a single step
+pub type MetaEdit = Vec<ItemChange>;
+
+impl fmt::Display for ItemChange {
+    /// Formats the change like `j_blueprint: discovered false → true`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (before, after) = match (&self.before, &self.after) {
+            (Some(before), Some(after)) => (before, after),
+            (None, _) => return write!(f, "{}: added", self.name),
+            (_, None) => return write!(f, "{}: removed", self.name),
+        };
+        let flags = [
+            ("alerted", before.alerted, after.alerted),
+            ("discovered", before.discovered, after.discovered),
+            ("unlocked", before.unlocked, after.unlocked),
+        ];
+        let changed: Vec<String> = flags
+            .iter()
+            .filter(|(_, before, after)| before != after)
+            .map(|(flag, before, after)| {
+                format!("{} {} → {}", flag, before, after)
+            })
+            .collect();
+        write!(f, "{}: {}", self.name, changed.join(", "))
+    }
+}
+
+/// Works out the changes between two sets of items, sorted by name
+pub fn diff_items(
+    before: &HashMap<String, MetaItem>,
+    after: &HashMap<String, MetaItem>,
+) -> MetaEdit {
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| before.get(*name) != after.get(*name))
+        .map(|name| ItemChange {
+            name: name.clone(),
+            before: before.get(name).cloned(),
+            after: after.get(name).cloned(),
+        })
+        .collect()
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone)]
+use crate::enums::MetaFlag;
+
+#[derive(Debug, Clone, PartialEq)]
 
 /// MetaItem struct for the meta struct
 /// This struct is used to store the state of the items in the meta file
@@ -16,6 +18,8 @@ pub struct MetaItem {
     poss_discover: bool,
     /// The poss_unlock field is used to check if the item is unlocked with 100% completion
     poss_unlock: bool,
+    /// The prefix of the mod the item is from, if it is modded
+    mod_prefix: Option<String>,
 }
 
 impl MetaItem {
@@ -40,6 +44,60 @@ impl MetaItem {
             poss_alert,
             poss_discover,
             poss_unlock,
+            mod_prefix: None,
+        }
+    }
+    /// Returns the item marked as being from a mod
+    pub fn with_mod(mut self, mod_prefix: Option<&str>) -> Self {
+        self.mod_prefix = mod_prefix.map(str::to_string);
+        self
+    }
+    /// Returns the prefix of the mod the item is from, if it is modded
+    pub fn mod_prefix(&self) -> Option<&str> {
+        self.mod_prefix.as_deref()
+    }
+    /// Combines the flags of two items, applying f to each pair of flags
+    /// Which flags the result can have is taken from both items
+    pub fn combine(&self, other: &MetaItem, f: fn(bool, bool) -> bool) -> Self {
+        MetaItem {
+            alerted: f(self.alerted, other.alerted),
+            discovered: f(self.discovered, other.discovered),
+            unlocked: f(self.unlocked, other.unlocked),
+            poss_alert: self.poss_alert || other.poss_alert,
+            poss_discover: self.poss_discover || other.poss_discover,
+            poss_unlock: self.poss_unlock || other.poss_unlock,
+            mod_prefix: self.mod_prefix.clone().or(other.mod_prefix.clone()),
+        }
+    }
+    /// Returns the item with every flag cleared
+    pub fn cleared(&self) -> Self {
+        self.combine(self, |_, _| false)
+    }
+    /// Returns the value of a flag
+    pub fn flag(&self, flag: MetaFlag) -> bool {
+        match flag {
+            MetaFlag::Alerted => self.alerted,
+            MetaFlag::Discovered => self.discovered,
+            MetaFlag::Unlocked => self.unlocked,
+        }
+    }
+    /// Sets a flag, if the item can have it
+    pub fn set_flag(&mut self, flag: MetaFlag, value: bool) {
+        if !self.can_have(flag) {
+            return;
+        }
+        match flag {
+            MetaFlag::Alerted => self.alerted = value,
+            MetaFlag::Discovered => self.discovered = value,
+            MetaFlag::Unlocked => self.unlocked = value,
+        }
+    }
+    /// Returns whether the item can have a flag
+    pub fn can_have(&self, flag: MetaFlag) -> bool {
+        match flag {
+            MetaFlag::Alerted => self.poss_alert,
+            MetaFlag::Discovered => self.poss_discover,
+            MetaFlag::Unlocked => self.poss_unlock,
         }
     }
     /// Returns self.poss_alert
@@ -0,0 +1,91 @@
+use super::{Meta, MetaItem};
+use crate::enums::{ItemCategory, MetaFlag};
+
+/// Checks if an item is from a mod, and of a category if one is given
+fn in_mod(
+    name: &str,
+    item: &MetaItem,
+    mod_prefix: &str,
+    category: Option<ItemCategory>,
+) -> bool {
+    item.mod_prefix() == Some(mod_prefix)
+        && category.is_none_or(|c| ItemCategory::of(name) == Some(c))
+}
+
+impl Meta {
+    /// Returns the prefixes of the mods the items are from, sorted
+    pub fn mods(&self) -> Vec<String> {
+        let mut mods: Vec<String> = self
+            .items
+            .values()
+            .filter_map(|item| item.mod_prefix())
+            .map(str::to_string)
+            .collect();
+        mods.sort();
+        mods.dedup();
+        mods
+    }
+
+    /// Returns the prefix of the mod an item is from, if it is modded
+    pub fn item_mod(&self, name: &str) -> Option<&str> {
+        self.items.get(name).and_then(|item| item.mod_prefix())
+    }
+
+    /// Returns the names of the items from a mod, sorted
+    pub fn mod_items(&self, mod_prefix: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .items
+            .iter()
+            .filter(|(name, item)| in_mod(name, item, mod_prefix, None))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Sets one flag on the items from a mod, of every category if none is
+    /// given
+    pub fn set_flag_mod(
+        &mut self,
+        mod_prefix: &str,
+        category: Option<ItemCategory>,
+        flag: MetaFlag,
+        value: bool,
+    ) {
+        for (name, item) in self.items.iter_mut() {
+            if in_mod(name, item, mod_prefix, category) {
+                item.set_flag(flag, value);
+            }
+        }
+    }
+
+    /// Sets every flag on the items from a mod, like unlock_all_type
+    pub fn unlock_mod(
+        &mut self,
+        mod_prefix: &str,
+        category: Option<ItemCategory>,
+    ) {
+        for flag in MetaFlag::ALL {
+            self.set_flag_mod(mod_prefix, category, flag, true);
+        }
+    }
+
+    /// Clears every flag on the items from a mod, like lock_all_type
+    pub fn lock_mod(
+        &mut self,
+        mod_prefix: &str,
+        category: Option<ItemCategory>,
+    ) {
+        for flag in MetaFlag::ALL {
+            self.set_flag_mod(mod_prefix, category, flag, false);
+        }
+    }
+
+    /// Removes every item from a mod, like one that is no longer installed
+    /// Returns how many items were removed
+    pub fn remove_mod(&mut self, mod_prefix: &str) -> usize {
+        let before = self.items.len();
+        self.items.retain(|name, item| !in_mod(name, item, mod_prefix, None));
+        before - self.items.len()
+    }
+}
@@ -0,0 +1,75 @@
+use super::Meta;
+use crate::enums::ItemCategory;
+use crate::enums::MetaFlag;
+
+/// How many items have a flag set, out of the items that can have it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlagCount {
+    /// The items that have the flag set
+    pub done: usize,
+    /// The items that can have the flag
+    pub total: usize,
+}
+
+impl FlagCount {
+    /// Returns how much of the flag is done, from 0 to 1
+    /// A category with no items that can have the flag counts as done
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.done as f32 / self.total as f32
+    }
+}
+
+/// How complete a category of items is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategoryStats {
+    /// The category the counts are for
+    pub category: ItemCategory,
+    /// The items that have been alerted
+    pub alerted: FlagCount,
+    /// The items that have been discovered
+    pub discovered: FlagCount,
+    /// The items that have been unlocked
+    pub unlocked: FlagCount,
+}
+
+impl Meta {
+    /// Counts how complete a category is
+    /// The items in the registry are the reference, so modded items it does
+    /// not know are not counted
+    pub fn category_stats(&self, category: ItemCategory) -> CategoryStats {
+        let mut stats = CategoryStats {
+            category,
+            alerted: FlagCount::default(),
+            discovered: FlagCount::default(),
+            unlocked: FlagCount::default(),
+        };
+        for entry in self.registry().category(category) {
+            let item = self.items.get(&entry.key);
+            let counts = [
+                (MetaFlag::Alerted, &mut stats.alerted),
+                (MetaFlag::Discovered, &mut stats.discovered),
+                (MetaFlag::Unlocked, &mut stats.unlocked),
+            ];
+            for (flag, count) in counts {
+                if entry.can_have(flag) {
+                    count.total += 1;
+                    if item.is_some_and(|i| i.flag(flag)) {
+                        count.done += 1;
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    /// Counts how complete every category is, see category_stats
+    pub fn stats(&self) -> Vec<CategoryStats> {
+        ItemCategory::ALL
+            .into_iter()
+            .map(|category| self.category_stats(category))
+            .collect()
+    }
+}
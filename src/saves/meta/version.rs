@@ -0,0 +1,68 @@
+use super::Meta;
+use crate::lua::{self, SaveValue};
+use crate::registry::{Registry, registries};
+
+use std::path::Path;
+
+impl Meta {
+    /// Returns the version of the game the meta is from, if it is known
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Sets the version of the game the meta is from
+    /// Items a new profile of that version has are added if they are missing
+    pub fn set_version(&mut self, version: Option<String>) {
+        self.version = version;
+        self.add_missing_defaults();
+    }
+
+    /// Takes the game version from the save.jkr next to a meta file, for meta
+    /// files that do not have one of their own
+    /// Nothing changes if the meta already has a version or there is no run
+    /// saved next to it. The version found is not written to the meta file.
+    pub fn find_version(&mut self, path: &Path) {
+        if self.version.is_some() {
+            return;
+        }
+        let version = std::fs::read(path.with_file_name("save.jkr"))
+            .ok()
+            .and_then(|data| lua::parse_save_data(&data).ok())
+            .and_then(|save| {
+                save.get_path("VERSION")
+                    .and_then(SaveValue::as_str)
+                    .map(str::to_string)
+            });
+        if version.is_some() {
+            self.set_version(version);
+        }
+    }
+
+    /// Returns the items the meta is checked against, the ones for its
+    /// version if there are any
+    pub fn registry(&self) -> &'static Registry {
+        registries().for_version(self.version())
+    }
+
+    /// Checks if there are items for the version of the game the meta is
+    /// from
+    /// A meta with no known version is checked against the base items, so it
+    /// counts as known
+    pub fn knows_version(&self) -> bool {
+        self.version().is_none_or(|version| registries().knows(version))
+    }
+
+    /// Explains which items are used instead, when there are none for the
+    /// version of the game the meta is from
+    pub fn version_warning(&self) -> Option<String> {
+        if self.knows_version() {
+            return None;
+        }
+        let fallback = self.registry().version().unwrap_or("unknown");
+        Some(format!(
+            "there is no item data for version {}, the items of {} are used",
+            self.version().unwrap_or_default(),
+            fallback
+        ))
+    }
+}
@@ -1,13 +1,29 @@
-use crate::lua::LuaContext;
+use crate::enums::{SaveError, SaveKind};
+use crate::lua::{self, SaveKey, SaveTable, SaveValue};
+use fuzzy_matcher::skim::SkimMatcherV2;
 use std::collections::HashMap;
 mod joker_usage;
 
 mod career_stats;
+mod challenge_progress;
 mod consumable_usage;
-use career_stats::CareerStats;
+mod deck_usage;
+mod filters;
+mod hand_usage;
+mod high_score;
+mod memory;
+mod progress;
+pub use career_stats::CareerStats;
+pub use challenge_progress::ChallengeProgress;
 
-use consumable_usage::ConsumableUsage;
-use joker_usage::JokerUsage;
+pub use consumable_usage::ConsumableUsage;
+pub use deck_usage::DeckUsage;
+pub use filters::Filters;
+pub use hand_usage::HandUsage;
+pub use high_score::HighScore;
+pub use joker_usage::JokerUsage;
+pub use memory::Memory;
+pub use progress::{Progress, Tally};
 
 // Profile Table
 // MEMORY  table: 0x7e12dc02a5c0
@@ -84,25 +100,356 @@ use joker_usage::JokerUsage;
 // Individual Card Table
 //count   42
 //order   17
+/// Profile struct for the profile save file
+/// The tables of the file are read into the typed fields below, everything
+/// else is kept as it was read so saving never loses anything
+#[derive(Default)]
 pub struct Profile {
     pub name: String,
     pub challenges_unlocked: u8,
     pub stake: u8,
     pub joker_usage: HashMap<String, JokerUsage>,
     pub consumable_usage: HashMap<String, ConsumableUsage>,
+    pub voucher_usage: HashMap<String, ConsumableUsage>,
+    pub deck_usage: HashMap<String, DeckUsage>,
+    pub hand_usage: HashMap<String, HandUsage>,
+    /// The highest stake won with each deck
+    pub deck_stakes: HashMap<String, u8>,
+    pub challenge_progress: ChallengeProgress,
+    pub high_scores: HashMap<String, HighScore>,
+    pub progress: Progress,
+    pub memory: Memory,
     pub career_stats: CareerStats,
+    /// The filters for the different sections of the editor
+    pub filters: Filters,
+    /// The matcher for the fuzzy search
+    pub matcher: SkimMatcherV2,
+    /// The table the profile was read from
+    raw: SaveTable,
 }
 
 impl Profile {
     /// Creates a new Profile
     pub fn new() -> Self {
-        todo!("Placeholder");
+        Profile::default()
     }
 
-    pub fn to_lua_data(
-        &self,
-        lua: &LuaContext,
-    ) -> Result<Vec<u8>, mlua::Error> {
-        todo!("Implement Profile::to_lua_data");
+    /// Convert the raw data from the save file into a Profile struct
+    pub fn from_lua_table(data: Vec<u8>) -> Result<Self, SaveError> {
+        Profile::from_save_value(lua::parse_save_data(&data)?)
+    }
+
+    /// Convert a decoded save file into a Profile struct
+    pub fn from_save_value(value: SaveValue) -> Result<Self, SaveError> {
+        SaveError::check_kind(&value, SaveKind::Profile)?;
+        let raw = match value {
+            SaveValue::Table(table) => table,
+            _ => {
+                return Err(SaveError::WrongFileType {
+                    expected: SaveKind::Profile,
+                    found: SaveKind::Unknown,
+                });
+            }
+        };
+
+        let career_stats =
+            CareerStats::from_save_table(raw.access_subtable("career_stats")?);
+
+        Ok(Profile {
+            name: read_string(&raw, "name"),
+            challenges_unlocked: read_u8(&raw, "challenges_unlocked"),
+            stake: read_u8(&raw, "stake"),
+            joker_usage: read_map(
+                &raw,
+                "joker_usage",
+                JokerUsage::from_save_table,
+            ),
+            consumable_usage: read_map(
+                &raw,
+                "consumeable_usage",
+                ConsumableUsage::from_save_table,
+            ),
+            voucher_usage: read_map(
+                &raw,
+                "voucher_usage",
+                ConsumableUsage::from_save_table,
+            ),
+            deck_usage: read_map(
+                &raw,
+                "deck_usage",
+                DeckUsage::from_save_table,
+            ),
+            hand_usage: read_map(
+                &raw,
+                "hand_usage",
+                HandUsage::from_save_table,
+            ),
+            deck_stakes: read_deck_stakes(&raw),
+            challenge_progress: raw
+                .get_table("challenge_progress")
+                .map(ChallengeProgress::from_save_table)
+                .unwrap_or_default(),
+            high_scores: read_map(
+                &raw,
+                "high_scores",
+                HighScore::from_save_table,
+            ),
+            progress: raw
+                .get_table("progress")
+                .map(Progress::from_save_table)
+                .unwrap_or_default(),
+            memory: raw
+                .get_table("MEMORY")
+                .map(Memory::from_save_table)
+                .unwrap_or_default(),
+            career_stats,
+            filters: Filters::new(),
+            matcher: SkimMatcherV2::default(),
+            raw,
+        })
+    }
+
+    /// Convert the struct into the raw data that is used in the save file
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        lua::to_save_data(&SaveValue::Table(self.to_save_table()))
+    }
+
+    /// Convert the struct into the table that is written to the save file
+    /// Starts from the table that was read, so unmodelled keys are kept
+    pub fn to_save_table(&self) -> SaveTable {
+        let mut raw = self.raw.clone();
+
+        write_string(&mut raw, "name", &self.name);
+        write_u8(&mut raw, "challenges_unlocked", self.challenges_unlocked);
+        write_u8(&mut raw, "stake", self.stake);
+
+        write_map(
+            &mut raw,
+            "joker_usage",
+            &self.joker_usage,
+            JokerUsage::write_to,
+        );
+        write_map(
+            &mut raw,
+            "consumeable_usage",
+            &self.consumable_usage,
+            ConsumableUsage::write_to,
+        );
+        write_map(
+            &mut raw,
+            "voucher_usage",
+            &self.voucher_usage,
+            ConsumableUsage::write_to,
+        );
+        write_map(
+            &mut raw,
+            "deck_usage",
+            &self.deck_usage,
+            DeckUsage::write_to,
+        );
+        write_map(
+            &mut raw,
+            "hand_usage",
+            &self.hand_usage,
+            HandUsage::write_to,
+        );
+        write_map(
+            &mut raw,
+            "high_scores",
+            &self.high_scores,
+            HighScore::write_to,
+        );
+        write_deck_stakes(&mut raw, &self.deck_stakes);
+
+        let challenge_progress = &self.challenge_progress;
+        if let Some(table) = section_mut(
+            &mut raw,
+            "challenge_progress",
+            challenge_progress.completed.is_empty()
+                && challenge_progress.unlocked.is_empty(),
+        ) {
+            challenge_progress.write_to(table);
+        }
+        if let Some(table) =
+            section_mut(&mut raw, "progress", self.progress.tallies.is_empty())
+        {
+            self.progress.write_to(table);
+        }
+        if let Some(table) =
+            section_mut(&mut raw, "MEMORY", self.memory == Memory::default())
+        {
+            self.memory.write_to(table);
+        }
+        self.career_stats.write_to(raw.subtable_mut("career_stats"));
+
+        raw
+    }
+}
+
+/// Reads a number from a table as a count
+/// Anything missing, negative or not a number counts as 0
+fn read_count(table: &SaveTable, key: impl Into<SaveKey>) -> u128 {
+    match table.get(key).and_then(SaveValue::as_f64) {
+        Some(value) if value > 0.0 => value as u128,
+        _ => 0,
+    }
+}
+
+/// Reads a count that is kept in a u8, like a stake, see read_count
+fn read_u8(table: &SaveTable, key: impl Into<SaveKey>) -> u8 {
+    read_count(table, key).min(u8::MAX as u128) as u8
+}
+
+/// Reads a number from a table, anything missing or not a number is 0
+fn read_float(table: &SaveTable, key: impl Into<SaveKey>) -> f64 {
+    table.get(key).and_then(SaveValue::as_f64).unwrap_or(0.0)
+}
+
+/// Reads a string from a table, anything missing or not a string is empty
+fn read_string(table: &SaveTable, key: impl Into<SaveKey>) -> String {
+    table.get(key).and_then(SaveValue::as_str).unwrap_or_default().to_string()
+}
+
+/// Writes a number, leaving the table alone if it still reads as the value
+/// A missing number is read as 0, so it is not added just to hold a 0, and
+/// a value that is not a number is kept until the number is edited
+fn write_number(table: &mut SaveTable, key: impl Into<SaveKey>, value: f64) {
+    let key = key.into();
+    if read_float(table, key.clone()) != value {
+        table.set_number(key, value);
+    }
+}
+
+/// Writes a count, leaving the table alone if it still reads as the count
+/// Values like 2.5 or -1 that read_count changes are kept until the count
+/// is edited
+fn write_count(table: &mut SaveTable, key: impl Into<SaveKey>, value: u128) {
+    let key = key.into();
+    if read_count(table, key.clone()) != value {
+        table.set_number(key, value as f64);
+    }
+}
+
+/// Writes a count that is kept in a u8, see write_count
+fn write_u8(table: &mut SaveTable, key: impl Into<SaveKey>, value: u8) {
+    let key = key.into();
+    if read_u8(table, key.clone()) != value {
+        table.set_number(key, value as f64);
+    }
+}
+
+/// Writes a string, leaving the table alone if it already holds it
+/// A missing string is read as empty, so it is not added just to hold one
+fn write_string(table: &mut SaveTable, key: impl Into<SaveKey>, value: &str) {
+    let key = key.into();
+    if !value.is_empty() || table.contains_key(key.clone()) {
+        table.set_string(key, value);
+    }
+}
+
+/// Gets a subtable of the profile to write a section into
+/// Returns None for a missing section that would be written empty, so it is
+/// not added to the file
+fn section_mut<'a>(
+    raw: &'a mut SaveTable,
+    key: &str,
+    is_empty: bool,
+) -> Option<&'a mut SaveTable> {
+    if is_empty && raw.get_table(key).is_none() {
+        return None;
+    }
+    Some(raw.subtable_mut(key))
+}
+
+/// Reads a table of tables keyed by name, such as joker_usage
+fn read_map<T>(
+    raw: &SaveTable,
+    key: &str,
+    read: impl Fn(&SaveTable) -> T,
+) -> HashMap<String, T> {
+    let mut map = HashMap::new();
+    for (key, value) in raw.get_table(key).into_iter().flat_map(SaveTable::iter)
+    {
+        if let (SaveKey::Str(name), Some(table)) = (key, value.as_table()) {
+            map.insert(name.clone(), read(table));
+        }
+    }
+    map
+}
+
+/// Writes a table of tables keyed by name, such as joker_usage
+/// Names that are no longer in the map are removed from the table
+fn write_map<T>(
+    raw: &mut SaveTable,
+    key: &str,
+    map: &HashMap<String, T>,
+    write: impl Fn(&T, &mut SaveTable),
+) {
+    let Some(table) = section_mut(raw, key, map.is_empty()) else {
+        return;
+    };
+    let removed: Vec<SaveKey> = table
+        .iter()
+        .filter(|(key, value)| match key {
+            SaveKey::Str(name) => {
+                value.as_table().is_some() && !map.contains_key(name)
+            }
+            SaveKey::Int(_) => false,
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in removed {
+        table.remove(key);
+    }
+
+    // Sorted so new names are added in the same order every time
+    let mut names: Vec<&String> = map.keys().collect();
+    names.sort();
+    for name in names {
+        write(&map[name], table.subtable_mut(name.as_str()));
+    }
+}
+
+/// Reads the deck_stakes table, which maps deck keys to stakes
+fn read_deck_stakes(raw: &SaveTable) -> HashMap<String, u8> {
+    let mut deck_stakes = HashMap::new();
+    let table = raw.get_table("deck_stakes");
+    for (key, _) in table.into_iter().flat_map(SaveTable::iter) {
+        if let SaveKey::Str(deck) = key {
+            let stake = read_u8(table.unwrap(), deck.as_str()); // Safe to unwrap because we are iterating over it
+            deck_stakes.insert(deck.clone(), stake);
+        }
+    }
+    deck_stakes
+}
+
+/// Writes the deck_stakes table
+fn write_deck_stakes(raw: &mut SaveTable, deck_stakes: &HashMap<String, u8>) {
+    let Some(table) = section_mut(raw, "deck_stakes", deck_stakes.is_empty())
+    else {
+        return;
+    };
+    let removed: Vec<SaveKey> = table
+        .iter()
+        .filter(|(key, _)| {
+            matches!(key, SaveKey::Str(deck) if !deck_stakes.contains_key(deck))
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in removed {
+        table.remove(key);
+    }
+
+    let mut decks: Vec<&String> = deck_stakes.keys().collect();
+    decks.sort();
+    for deck in decks {
+        // A value that reads as the same stake is kept as it is, so ones
+        // that are not numbers or do not fit in a u8 are not overwritten
+        let stake = deck_stakes[deck];
+        if !table.contains_key(deck.as_str())
+            || read_u8(table, deck.as_str()) != stake
+        {
+            table.set_number(deck.as_str(), stake as f64);
+        }
     }
 }
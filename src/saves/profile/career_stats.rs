@@ -1,3 +1,6 @@
+use super::{read_count, read_float, write_count, write_number};
+use crate::lua::SaveTable;
+
 pub struct CareerStats {
     // All prefixed with "c_"
     pub round_interest_cap_streak: usize,
@@ -52,4 +55,88 @@ impl CareerStats {
     pub fn new() -> Self {
         CareerStats::default()
     }
+    /// Reads the career_stats table of a profile
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        let mut stats = CareerStats::new();
+        for (key, value) in stats.counts_mut() {
+            *value = read_count(table, key) as usize;
+        }
+        for (key, value) in stats.amounts_mut() {
+            *value = read_float(table, key);
+        }
+        stats
+    }
+    /// Writes the stats into the career_stats table of a profile
+    /// Stats that did not change are left as they are
+    pub fn write_to(&self, table: &mut SaveTable) {
+        for (key, value) in self.counts() {
+            write_count(table, key, value as u128);
+        }
+        for (key, value) in self.amounts() {
+            write_number(table, key, value);
+        }
+    }
+    /// Returns the whole number stats with the keys they are saved under
+    pub fn counts(&self) -> [(&'static str, usize); 18] {
+        [
+            ("c_round_interest_cap_streak", self.round_interest_cap_streak),
+            ("c_cards_played", self.cards_played),
+            ("c_planetarium_used", self.planetarium_used),
+            ("c_jokers_sold", self.jokers_sold),
+            ("c_hands_played", self.hands_played),
+            ("c_cards_sold", self.cards_sold),
+            ("c_wins", self.wins),
+            ("c_playing_cards_bought", self.playing_cards_bought),
+            ("c_shop_rerolls", self.shop_rerolls),
+            ("c_rounds", self.rounds),
+            ("c_tarot_reading_used", self.tarot_reading_used),
+            ("c_losses", self.losses),
+            ("c_cards_discarded", self.cards_discarded),
+            ("c_single_hand_round_streak", self.single_hand_round_streak),
+            ("c_face_cards_played", self.face_cards_played),
+            ("c_tarots_bought", self.tarots_bought),
+            ("c_vouchers_bought", self.vouchers_bought),
+            ("c_planets_bought", self.planets_bought),
+        ]
+    }
+    /// Returns mutable references to the whole number stats with their keys
+    pub fn counts_mut(&mut self) -> [(&'static str, &mut usize); 18] {
+        [
+            (
+                "c_round_interest_cap_streak",
+                &mut self.round_interest_cap_streak,
+            ),
+            ("c_cards_played", &mut self.cards_played),
+            ("c_planetarium_used", &mut self.planetarium_used),
+            ("c_jokers_sold", &mut self.jokers_sold),
+            ("c_hands_played", &mut self.hands_played),
+            ("c_cards_sold", &mut self.cards_sold),
+            ("c_wins", &mut self.wins),
+            ("c_playing_cards_bought", &mut self.playing_cards_bought),
+            ("c_shop_rerolls", &mut self.shop_rerolls),
+            ("c_rounds", &mut self.rounds),
+            ("c_tarot_reading_used", &mut self.tarot_reading_used),
+            ("c_losses", &mut self.losses),
+            ("c_cards_discarded", &mut self.cards_discarded),
+            ("c_single_hand_round_streak", &mut self.single_hand_round_streak),
+            ("c_face_cards_played", &mut self.face_cards_played),
+            ("c_tarots_bought", &mut self.tarots_bought),
+            ("c_vouchers_bought", &mut self.vouchers_bought),
+            ("c_planets_bought", &mut self.planets_bought),
+        ]
+    }
+    /// Returns the money stats with the keys they are saved under
+    pub fn amounts(&self) -> [(&'static str, f64); 2] {
+        [
+            ("c_dollars_earned", self.dollars_earned),
+            ("c_shop_dollars_spent", self.shop_dollars_spent),
+        ]
+    }
+    /// Returns mutable references to the money stats with their keys
+    pub fn amounts_mut(&mut self) -> [(&'static str, &mut f64); 2] {
+        [
+            ("c_dollars_earned", &mut self.dollars_earned),
+            ("c_shop_dollars_spent", &mut self.shop_dollars_spent),
+        ]
+    }
 }
@@ -0,0 +1,61 @@
+use crate::lua::{SaveKey, SaveTable, SaveValue};
+use std::collections::HashSet;
+
+/// Which challenges have been unlocked and completed
+#[derive(Default)]
+pub struct ChallengeProgress {
+    /// The ids of the completed challenges
+    pub completed: HashSet<String>,
+    /// The ids of the unlocked challenges
+    pub unlocked: HashSet<String>,
+}
+
+impl ChallengeProgress {
+    /// Reads the challenge_progress table of a profile
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        ChallengeProgress {
+            completed: read_set(table.get_table("completed")),
+            unlocked: read_set(table.get_table("unlocked")),
+        }
+    }
+    /// Writes the progress into the challenge_progress table of a profile
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_set(table.subtable_mut("completed"), &self.completed);
+        write_set(table.subtable_mut("unlocked"), &self.unlocked);
+    }
+}
+
+/// Reads the ids that are set to true in a table
+fn read_set(table: Option<&SaveTable>) -> HashSet<String> {
+    let mut set = HashSet::new();
+    for (key, value) in table.into_iter().flat_map(SaveTable::iter) {
+        if let SaveKey::Str(id) = key
+            && value.is_truthy()
+        {
+            set.insert(id.clone());
+        }
+    }
+    set
+}
+
+/// Makes a table hold exactly the ids in the set
+fn write_set(table: &mut SaveTable, set: &HashSet<String>) {
+    let removed: Vec<SaveKey> = table
+        .iter()
+        .filter(|(key, value)| match key {
+            SaveKey::Str(id) => value.is_truthy() && !set.contains(id),
+            SaveKey::Int(_) => false,
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in removed {
+        table.remove(key);
+    }
+    let mut ids: Vec<&String> = set.iter().collect();
+    ids.sort();
+    for id in ids {
+        if !table.get(id.as_str()).is_some_and(SaveValue::is_truthy) {
+            table.insert(id.as_str(), SaveValue::Bool(true));
+        }
+    }
+}
@@ -1,4 +1,24 @@
+use super::{read_count, write_count};
+use crate::lua::SaveTable;
+
+/// How often a consumable has been used
+/// Vouchers are tracked the same way, so this is used for them too
 pub struct ConsumableUsage {
     pub count: u128,
     pub order: u128,
 }
+
+impl ConsumableUsage {
+    /// Reads the usage table of a single consumable
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        ConsumableUsage {
+            count: read_count(table, "count"),
+            order: read_count(table, "order"),
+        }
+    }
+    /// Writes the usage into the table of a single consumable
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_count(table, "count", self.count);
+        write_count(table, "order", self.order);
+    }
+}
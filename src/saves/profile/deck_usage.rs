@@ -0,0 +1,5 @@
+use super::JokerUsage;
+
+/// How often a deck has been used, and how the runs with it went
+/// Decks are tracked exactly the same way as jokers
+pub type DeckUsage = JokerUsage;
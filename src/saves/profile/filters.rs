@@ -0,0 +1,21 @@
+#[derive(Debug, Clone, Default)]
+
+/// Filters for the different sections of the profile editor
+/// All the fields are public as they are used as references in the egui App
+pub struct Filters {
+    /// Filter for joker usage
+    pub joker: String,
+    /// Filter for consumable usage
+    pub consumable: String,
+    /// Filter for deck stakes
+    pub deck: String,
+    /// Filter for challenges
+    pub challenge: String,
+}
+
+impl Filters {
+    /// Create a new Filters struct with empty strings
+    pub fn new() -> Self {
+        Filters::default()
+    }
+}
@@ -0,0 +1,24 @@
+use super::{read_count, read_string, write_count, write_string};
+use crate::lua::SaveTable;
+
+/// How often a poker hand has been played
+pub struct HandUsage {
+    pub count: u128,
+    /// The name of the hand, which the game sorts the hands by
+    pub order: String,
+}
+
+impl HandUsage {
+    /// Reads the usage table of a single hand
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        HandUsage {
+            count: read_count(table, "count"),
+            order: read_string(table, "order"),
+        }
+    }
+    /// Writes the usage into the table of a single hand
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_count(table, "count", self.count);
+        write_string(table, "order", &self.order);
+    }
+}
@@ -0,0 +1,31 @@
+use super::{read_float, read_string, write_number, write_string};
+use crate::lua::SaveTable;
+
+/// A single entry of the high scores, such as the best hand
+pub struct HighScore {
+    /// The name the game shows for the score
+    pub label: String,
+    /// The score itself
+    pub amt: f64,
+    /// The total the score is out of, only the collection has one
+    pub tot: Option<f64>,
+}
+
+impl HighScore {
+    /// Reads the table of a single high score
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        HighScore {
+            label: read_string(table, "label"),
+            amt: read_float(table, "amt"),
+            tot: table.contains_key("tot").then(|| read_float(table, "tot")),
+        }
+    }
+    /// Writes the score into the table of a single high score
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_string(table, "label", &self.label);
+        write_number(table, "amt", self.amt);
+        if let Some(tot) = self.tot {
+            write_number(table, "tot", tot);
+        }
+    }
+}
@@ -1,8 +1,52 @@
-use std::u128;
+use super::{read_count, section_mut, write_count};
+use crate::lua::{SaveKey, SaveTable};
 
+/// How often a joker has been used, and how the runs it was in went
+/// The wins and losses are per stake, with white stake first
 pub struct JokerUsage {
     pub count: u128,
     pub order: u128,
     pub wins: [u128; 8],
     pub losses: [u128; 8],
 }
+
+impl JokerUsage {
+    /// Reads the usage table of a single joker
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        JokerUsage {
+            count: read_count(table, "count"),
+            order: read_count(table, "order"),
+            wins: read_stakes(table.get_table("wins")),
+            losses: read_stakes(table.get_table("losses")),
+        }
+    }
+    /// Writes the usage into the table of a single joker
+    /// Stakes past the eighth, which only mods add, are left as they are
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_count(table, "count", self.count);
+        write_count(table, "order", self.order);
+        for (key, stakes) in [("wins", &self.wins), ("losses", &self.losses)] {
+            if let Some(table) = section_mut(table, key, *stakes == [0; 8]) {
+                write_stakes(table, stakes);
+            }
+        }
+    }
+}
+
+/// Reads a table keyed by stake number
+fn read_stakes(table: Option<&SaveTable>) -> [u128; 8] {
+    let mut stakes = [0; 8];
+    if let Some(table) = table {
+        for (i, stake) in stakes.iter_mut().enumerate() {
+            *stake = read_count(table, SaveKey::Int(i as i64 + 1));
+        }
+    }
+    stakes
+}
+
+/// Writes a table keyed by stake number
+fn write_stakes(table: &mut SaveTable, stakes: &[u128; 8]) {
+    for (i, stake) in stakes.iter().enumerate() {
+        write_count(table, SaveKey::Int(i as i64 + 1), *stake);
+    }
+}
@@ -0,0 +1,31 @@
+use super::{read_string, read_u8, write_string, write_u8};
+use crate::lua::SaveTable;
+
+/// The deck and stake the last run was started with
+#[derive(PartialEq)]
+pub struct Memory {
+    /// The name of the deck, like "Red Deck"
+    pub deck: String,
+    pub stake: u8,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Memory { deck: "Red Deck".to_string(), stake: 1 }
+    }
+}
+
+impl Memory {
+    /// Reads the MEMORY table of a profile
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        Memory {
+            deck: read_string(table, "deck"),
+            stake: read_u8(table, "stake"),
+        }
+    }
+    /// Writes the memory into the MEMORY table of a profile
+    pub fn write_to(&self, table: &mut SaveTable) {
+        write_string(table, "deck", &self.deck);
+        write_u8(table, "stake", self.stake);
+    }
+}
@@ -0,0 +1,52 @@
+use super::{read_float, write_number};
+use crate::lua::{SaveKey, SaveTable};
+use std::collections::HashMap;
+
+/// A count of how much of something is done, out of how much there is
+pub struct Tally {
+    pub tally: f64,
+    pub of: f64,
+}
+
+/// The completion progress the game shows on the profile screen
+/// The game works this out again when it loads, but it is kept as is
+#[derive(Default)]
+pub struct Progress {
+    /// The tallies by name, such as joker_stickers or discovered
+    pub tallies: HashMap<String, Tally>,
+    pub overall_tally: f64,
+    pub overall_of: f64,
+}
+
+impl Progress {
+    /// Reads the progress table of a profile
+    pub fn from_save_table(table: &SaveTable) -> Self {
+        let mut tallies = HashMap::new();
+        for (key, value) in table.iter() {
+            if let (SaveKey::Str(name), Some(tally)) = (key, value.as_table()) {
+                tallies.insert(
+                    name.clone(),
+                    Tally {
+                        tally: read_float(tally, "tally"),
+                        of: read_float(tally, "of"),
+                    },
+                );
+            }
+        }
+        Progress {
+            tallies,
+            overall_tally: read_float(table, "overall_tally"),
+            overall_of: read_float(table, "overall_of"),
+        }
+    }
+    /// Writes the progress into the progress table of a profile
+    pub fn write_to(&self, table: &mut SaveTable) {
+        for (name, tally) in self.tallies.iter() {
+            let tally_table = table.subtable_mut(name.as_str());
+            write_number(tally_table, "tally", tally.tally);
+            write_number(tally_table, "of", tally.of);
+        }
+        write_number(table, "overall_tally", self.overall_tally);
+        write_number(table, "overall_of", self.overall_of);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::enums::{SaveError, SaveKind};
+use crate::lua::{self, SaveTable, SaveValue};
+
+// Save Table
+// STATE   5
+// BLIND   table: 0x75142c059620
+// tags    table: 0x75142c059ae0
+// VERSION 1.0.1o-FULL
+// GAME    table: 0x75142c0a8ce0
+// BACK    table: 0x75142c0596a0
+// cardAreas       table: 0x75142c059b20
+
+/// The whole number values of a run that can be edited
+/// Each is the label shown in the editor and the path it is saved under
+pub const SAVEGAME_COUNTS: [(&str, &str); 7] = [
+    ("Ante", "GAME.round_resets.ante"),
+    ("Round", "GAME.round"),
+    ("Hands", "GAME.round_resets.hands"),
+    ("Discards", "GAME.round_resets.discards"),
+    ("Joker Slots", "cardAreas.jokers.config.card_limit"),
+    ("Consumable Slots", "cardAreas.consumeables.config.card_limit"),
+    ("Hand Size", "cardAreas.hand.config.card_limit"),
+];
+
+/// SaveGame struct for the save file of a run in progress
+///
+/// Only the values that make sense to edit are read into fields, the rest of
+/// the run is kept as it was read. That includes the `MANUAL_REPLACE`
+/// markers the game leaves in place of objects, which it fills in on load.
+pub struct SaveGame {
+    /// The version of the game that wrote the file
+    pub version: String,
+    /// The state the game was in, such as the shop or a blind
+    pub state: i64,
+    /// The seed of the run
+    pub seed: String,
+    /// The name of the deck the run uses
+    pub deck: String,
+    /// The stake the run is on
+    pub stake: i64,
+    /// The money the player has
+    pub dollars: f64,
+    /// The values from SAVEGAME_COUNTS, in the same order
+    pub counts: [i64; 7],
+    /// The keys of the jokers the player has, in order
+    pub jokers: Vec<String>,
+    /// The table the run was read from
+    raw: SaveValue,
+}
+
+impl SaveGame {
+    /// Convert the raw data from the save file into a SaveGame struct
+    pub fn from_lua_table(data: Vec<u8>) -> Result<Self, SaveError> {
+        SaveGame::from_save_value(lua::parse_save_data(&data)?)
+    }
+
+    /// Convert a decoded save file into a SaveGame struct
+    pub fn from_save_value(raw: SaveValue) -> Result<Self, SaveError> {
+        SaveError::check_kind(&raw, SaveKind::Save)?;
+        let table = match raw.as_table() {
+            Some(table) => table,
+            None => {
+                return Err(SaveError::WrongFileType {
+                    expected: SaveKind::Save,
+                    found: SaveKind::Unknown,
+                });
+            }
+        };
+        table.access_subtable("GAME")?;
+        table.access_subtable("cardAreas")?;
+
+        let mut counts = [0; 7];
+        for (count, (_, path)) in counts.iter_mut().zip(SAVEGAME_COUNTS) {
+            *count = read_count(&raw, path);
+        }
+
+        let mut jokers = Vec::new();
+        if let Some(cards) = raw.get_path("cardAreas.jokers.cards") {
+            for (_, card) in
+                cards.as_table().into_iter().flat_map(SaveTable::iter)
+            {
+                if let Some(center) = card
+                    .get_path("save_fields.center")
+                    .and_then(SaveValue::as_str)
+                {
+                    jokers.push(center.to_string());
+                }
+            }
+        }
+
+        Ok(SaveGame {
+            version: read_string(&raw, "VERSION"),
+            state: raw
+                .get_path("STATE")
+                .and_then(SaveValue::as_i64)
+                .unwrap_or(0),
+            seed: read_string(&raw, "GAME.pseudorandom.seed"),
+            deck: read_string(&raw, "BACK.name"),
+            stake: raw
+                .get_path("GAME.stake")
+                .and_then(SaveValue::as_i64)
+                .unwrap_or(1),
+            dollars: raw
+                .get_path("GAME.dollars")
+                .and_then(SaveValue::as_f64)
+                .unwrap_or(0.0),
+            counts,
+            jokers,
+            raw,
+        })
+    }
+
+    /// Convert the struct into the raw data that is used in the save file
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        lua::to_save_data(&self.to_save_value())
+    }
+
+    /// Convert the struct into the value that is written to the save file
+    /// Only the values that were edited are written over the original table
+    pub fn to_save_value(&self) -> SaveValue {
+        let mut raw = self.raw.clone();
+        // The blind ante follows an edited ante, or the next blind is wrong
+        if read_count(&raw, SAVEGAME_COUNTS[0].1) != self.counts[0]
+            && raw.get_path("GAME.round_resets.blind_ante").is_some()
+        {
+            set_number(
+                &mut raw,
+                "GAME.round_resets.blind_ante",
+                self.counts[0] as f64,
+            );
+        }
+        set_number(&mut raw, "GAME.dollars", self.dollars);
+        for (count, (_, path)) in self.counts.iter().zip(SAVEGAME_COUNTS) {
+            // A count that still reads the same is kept, so one like 2.5 is
+            // not rounded until it is edited
+            if read_count(&raw, path) != *count {
+                set_number(&mut raw, path, *count as f64);
+            }
+        }
+        raw
+    }
+
+    /// Gets the value at a path of the run, for anything without a field
+    pub fn get_path(&self, path: &str) -> Option<&SaveValue> {
+        self.raw.get_path(path)
+    }
+}
+
+/// Reads a string at a path, anything missing or not a string is empty
+fn read_string(raw: &SaveValue, path: &str) -> String {
+    raw.get_path(path)
+        .and_then(SaveValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Reads a whole number at a path, anything missing or not a number is 0
+/// Floats are read as numbers too, cut down to a whole number
+fn read_count(raw: &SaveValue, path: &str) -> i64 {
+    raw.get_path(path).and_then(SaveValue::as_f64).unwrap_or(0.0) as i64
+}
+
+/// Sets a number at a path, see SaveTable::set_number
+/// A missing number was read as 0, so it is only added once it is edited
+fn set_number(raw: &mut SaveValue, path: &str, value: f64) {
+    if raw.get_path(path).and_then(SaveValue::as_f64).unwrap_or(0.0) == value {
+        return;
+    }
+    // The paths are all in tables the game always writes, so there is only
+    // no table on a file that is not really a save, which was checked on load
+    let Some((parent, key)) = path.rsplit_once('.') else {
+        return;
+    };
+    if let Some(table) =
+        raw.get_path_mut(parent).and_then(SaveValue::as_table_mut)
+    {
+        table.set_number(key, value);
+    }
+}
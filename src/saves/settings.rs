@@ -0,0 +1,155 @@
+use crate::enums::{SaveError, SaveKind};
+use crate::lua::{self, SaveTable, SaveValue};
+
+/// The languages the game ships with, as the key it saves and a label
+pub const LANGUAGES: [(&str, &str); 15] = [
+    ("en-us", "English"),
+    ("de", "German"),
+    ("es_419", "Spanish (Latin America)"),
+    ("es_ES", "Spanish (Spain)"),
+    ("fr", "French"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt_BR", "Portuguese (Brazil)"),
+    ("ru", "Russian"),
+    ("zh_CN", "Chinese (Simplified)"),
+    ("zh_TW", "Chinese (Traditional)"),
+];
+
+/// The game speeds the options menu lets you pick
+pub const GAME_SPEEDS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+
+/// Settings struct for the settings file the game shares between profiles
+///
+/// Only the common options are read into fields. Everything else, like the
+/// window and graphics options, is kept as it was read.
+pub struct Settings {
+    /// The language key, see LANGUAGES
+    pub language: String,
+    /// The speed of the game, see GAME_SPEEDS
+    pub game_speed: f64,
+    /// The main volume, from 0 to 100
+    pub volume: f64,
+    /// The music volume, from 0 to 100
+    pub music_volume: f64,
+    /// The game sounds volume, from 0 to 100
+    pub game_sounds_volume: f64,
+    /// The amount of screen shake, from 0 to 100
+    pub screenshake: f64,
+    /// Whether the high contrast cards are used
+    pub high_contrast: bool,
+    /// Whether crash reports are sent
+    pub crash_reports: bool,
+    /// The table the settings were read from
+    raw: SaveTable,
+}
+
+impl Settings {
+    /// Convert the raw data from the settings file into a Settings struct
+    pub fn from_lua_table(data: Vec<u8>) -> Result<Self, SaveError> {
+        Settings::from_save_value(lua::parse_save_data(&data)?)
+    }
+
+    /// Convert a decoded settings file into a Settings struct
+    pub fn from_save_value(value: SaveValue) -> Result<Self, SaveError> {
+        SaveError::check_kind(&value, SaveKind::Settings)?;
+        let raw = match value {
+            SaveValue::Table(table) => table,
+            _ => {
+                return Err(SaveError::WrongFileType {
+                    expected: SaveKind::Settings,
+                    found: SaveKind::Unknown,
+                });
+            }
+        };
+        let sound = raw.access_subtable("SOUND")?;
+
+        Ok(Settings {
+            language: raw
+                .get("language")
+                .and_then(SaveValue::as_str)
+                .unwrap_or(LANGUAGES[0].0)
+                .to_string(),
+            game_speed: read_number(&raw, "GAMESPEED", 1.0),
+            volume: read_number(sound, "volume", 50.0),
+            music_volume: read_number(sound, "music_volume", 100.0),
+            game_sounds_volume: read_number(sound, "game_sounds_volume", 100.0),
+            screenshake: read_screenshake(&raw),
+            high_contrast: read_bool(&raw, "colourblind_option"),
+            crash_reports: read_bool(&raw, "crashreports"),
+            raw,
+        })
+    }
+
+    /// Convert the struct into the raw data that is used in the settings file
+    pub fn to_lua_data(&self) -> Result<Vec<u8>, SaveError> {
+        lua::to_save_data(&SaveValue::Table(self.to_save_table()))
+    }
+
+    /// Convert the struct into the table that is written to the settings file
+    /// Only the options that were edited are written over the original table
+    pub fn to_save_table(&self) -> SaveTable {
+        let mut raw = self.raw.clone();
+
+        if raw.get("language").and_then(SaveValue::as_str)
+            != Some(&self.language)
+        {
+            raw.set_string("language", &self.language);
+        }
+        write_number(&mut raw, "GAMESPEED", 1.0, self.game_speed);
+
+        let sound = raw.subtable_mut("SOUND");
+        write_number(sound, "volume", 50.0, self.volume);
+        write_number(sound, "music_volume", 100.0, self.music_volume);
+        write_number(
+            sound,
+            "game_sounds_volume",
+            100.0,
+            self.game_sounds_volume,
+        );
+
+        // Older versions saved screen shake as a boolean, which is kept
+        // until the amount is changed
+        if read_screenshake(&raw) != self.screenshake {
+            raw.set_number("screenshake", self.screenshake);
+        }
+        if read_bool(&raw, "colourblind_option") != self.high_contrast {
+            raw.set_bool("colourblind_option", self.high_contrast);
+        }
+        if read_bool(&raw, "crashreports") != self.crash_reports {
+            raw.set_bool("crashreports", self.crash_reports);
+        }
+        raw
+    }
+}
+
+/// Reads a number, using the default the game uses if it is missing
+fn read_number(table: &SaveTable, key: &str, default: f64) -> f64 {
+    table.get(key).and_then(SaveValue::as_f64).unwrap_or(default)
+}
+
+/// Reads a boolean, a missing key is false like it is in the game
+fn read_bool(table: &SaveTable, key: &str) -> bool {
+    table.get(key).and_then(SaveValue::as_bool).unwrap_or(false)
+}
+
+/// Reads the screen shake amount, which used to be saved as a boolean
+fn read_screenshake(table: &SaveTable) -> f64 {
+    match table.get("screenshake") {
+        Some(SaveValue::Bool(true)) => 50.0,
+        Some(SaveValue::Bool(false)) => 0.0,
+        Some(value) => value.as_f64().unwrap_or(50.0),
+        None => 50.0,
+    }
+}
+
+/// Writes a number if it is not what the table already reads as
+fn write_number(table: &mut SaveTable, key: &str, default: f64, value: f64) {
+    if read_number(table, key, default) != value {
+        table.set_number(key, value);
+    }
+}
@@ -3,5 +3,5 @@ mod popups;
 
 pub mod drawings;
 
-pub use app::MyApp;
+pub use app::{MyApp, WINDOW_TITLE};
 pub use popups::Popup;
@@ -1,37 +1,120 @@
-use crate::enums::{PopupType, SaveType, TabState};
-use crate::lua::LuaContext;
+use crate::backup;
+use crate::config::Config;
+use crate::diff::Diff;
+use crate::enums::{
+    ExportFormat, MergeStrategy, PopupType, SaveError, SaveType, TabState,
+};
+use crate::locator::{self, SaveLocation};
+use crate::lua::SaveValue;
+use crate::presets::{self, Preset};
+use crate::registry::registries;
 #[cfg(feature = "dev")]
 use crate::saves::DevTest;
-use crate::saves::Meta;
+use crate::saves::{Meta, MetaEdit};
 
 use crate::ui::Popup;
-use crate::ui::drawings;
+use crate::ui::drawings::{self, BackupBrowser};
+use crate::watcher::{FileWatcher, POLL_INTERVAL};
 use eframe::egui::{Style, Visuals};
-use egui::Context;
+use egui::{Context, Key, KeyboardShortcut, Modifiers};
 
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender, channel};
 
+/// The title of the window when no file is open
+pub const WINDOW_TITLE: &str = "bal_save";
+
+/// A save that was opened and the file it was read from
+type LoadedFile = (SaveType, PathBuf);
+/// A file a save was written to and the data that was written
+type SavedFile = (PathBuf, Vec<u8>);
+
 pub struct MyApp {
-    save_channel: (Sender<SaveType>, Receiver<SaveType>),
+    save_channel: (Sender<LoadedFile>, Receiver<LoadedFile>),
+    /// The file a save was written to with Save As
+    path_channel: (Sender<SavedFile>, Receiver<SavedFile>),
     popup_channel: (Sender<Popup>, Receiver<Popup>),
+    /// A meta file picked to merge into the open one, and what is in it
+    merge_channel: (Sender<SavedFile>, Receiver<SavedFile>),
+    /// An exported meta file picked to import, and what is in it
+    import_channel: (Sender<SavedFile>, Receiver<SavedFile>),
+    /// A file picked to compare the open save with, and what is in it
+    compare_channel: (Sender<SavedFile>, Receiver<SavedFile>),
     save: Option<SaveType>,
+    /// The file the open save was loaded from, which Save writes back to
+    source: Option<PathBuf>,
+    /// The open save as it was loaded or last saved, to tell if it was edited
+    clean: Option<Vec<u8>>,
+    /// Watches the source file for the game writing it
+    watcher: Option<FileWatcher>,
+    /// The game's version of the open file, waiting for the user to choose
+    /// between it and their edits
+    pending_reload: Option<SaveType>,
+    /// How a merge combines the flags of the two meta files
+    merge_strategy: MergeStrategy,
+    /// The changes a merge or preset makes, waiting for the user to confirm
+    /// them
+    pending_changes: Option<MetaEdit>,
+    /// The format meta files are exported to
+    export_format: ExportFormat,
+    /// Whether an import may have items that are not known, like modded ones
+    import_unknown: bool,
+    /// The presets saved in the config directory
+    presets: Vec<Preset>,
+    /// The name the open meta is saved as a preset under
+    preset_name: String,
+    /// The file the open save was compared with, and what differs
+    comparison: Option<(PathBuf, Diff)>,
+    /// The title the window was last given
+    title: String,
     popup: Option<Popup>,
     tab: TabState,
     dark_mode: bool,
+    /// The save directories found on this machine
+    locations: Vec<SaveLocation>,
+    /// A file waiting for the user to confirm it can replace the open save
+    pending_open: Option<PathBuf>,
+    /// The settings of the app, like how many backups to keep
+    config: Config,
+    /// The backups that can be previewed and restored
+    backups: BackupBrowser,
     #[cfg(feature = "dev")]
     pub dev: DevTest,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
+        let config = Config::load();
         Self {
             save_channel: channel(),
+            path_channel: channel(),
             popup_channel: channel(),
+            compare_channel: channel(),
+            merge_channel: channel(),
+            import_channel: channel(),
             save: None,
-            popup: None,
+            source: None,
+            clean: None,
+            watcher: None,
+            pending_reload: None,
+            merge_strategy: MergeStrategy::default(),
+            pending_changes: None,
+            export_format: ExportFormat::default(),
+            import_unknown: false,
+            presets: presets::presets_dir()
+                .map(|dir| presets::list_presets(&dir))
+                .unwrap_or_default(),
+            preset_name: String::new(),
+            comparison: None,
+            title: WINDOW_TITLE.to_string(),
+            popup: skipped_items_popup(),
             tab: TabState::None,
             dark_mode: true,
+            locations: locator::find_save_locations(),
+            pending_open: None,
+            backups: BackupBrowser::new(&config.backup_dir()),
+            config,
             #[cfg(feature = "dev")]
             dev: DevTest::new(),
         }
@@ -53,8 +136,8 @@ impl MyApp {
         app
     }
 
-    fn make_meta(&mut self, ui: &egui::Ui) {
-        let meta_sender = self.save_channel.0.clone();
+    fn open_file(&mut self, ui: &egui::Ui) {
+        let save_sender = self.save_channel.0.clone();
         let popup_sender = self.popup_channel.0.clone();
         let task = rfd::AsyncFileDialog::new().pick_file();
         // Context is wrapped in an Arc so it's cheap to clone as per:
@@ -65,25 +148,442 @@ impl MyApp {
             let file = task.await;
             if let Some(file) = file {
                 let text = file.read().await;
-                let lua_context = LuaContext::new();
-
-                // let _ = lua_context
-                //     .make_meta_defaults(text.clone());
-
-                let meta = Meta::from_lua_table(lua_context, text.clone());
-                if meta.is_err() {
-                    let err = meta.err().unwrap();
-                    popup_sender
-                        .send(Popup::new(PopupType::ErrorLoad, err.to_string()))
-                        .unwrap();
-                    return;
+                let path = file.path().to_path_buf();
+
+                // The kind of file is worked out from what is in it, so any
+                // save file can be opened with the same button
+                match SaveType::from_lua_data(text) {
+                    Ok(mut save) => {
+                        save.find_version(&path);
+                        let _ = save_sender.send((save, path));
+                        ctx.request_repaint();
+                    }
+                    Err(err) => {
+                        popup_sender
+                            .send(Popup::new(
+                                PopupType::ErrorLoad,
+                                load_error_text(&err),
+                            ))
+                            .unwrap();
+                    }
                 }
+            }
+        });
+    }
+    /// Opens a file without a file picker, for the files the locator found
+    fn open_path(&mut self, path: PathBuf) {
+        match SaveType::from_file(&path) {
+            Ok(mut save) => {
+                save.find_version(&path);
+                self.set_save(save, Some(path));
+                self.tab = TabState::Editor;
+            }
+            Err(err) => {
+                self.popup = Some(Popup::new(
+                    PopupType::ErrorLoad,
+                    load_error_text(&err),
+                ));
+            }
+        }
+    }
+    /// Writes the open save back to the file it was loaded from
+    /// A save that was not loaded from a file is saved with Save As
+    fn save_file(&mut self) {
+        let Some(save) = &self.save else {
+            self.popup = Some(Popup::new(
+                PopupType::ErrorSave,
+                "Error: No Save Loaded".to_string(),
+            ));
+            return;
+        };
+        let Some(path) = &self.source else {
+            self.save_file_as();
+            return;
+        };
+
+        let result = save.to_lua_data().and_then(|data| {
+            backup::write_with_backup(
+                path,
+                &data,
+                &self.config.backup_dir(),
+                self.config.backups_to_keep,
+            )?;
+            Ok(data)
+        });
+        match result {
+            Ok(data) => {
+                self.clean = Some(data);
+                if let Some(watcher) = self.watcher.as_mut() {
+                    watcher.mark_seen();
+                }
+            }
+            Err(err) => {
+                self.popup =
+                    Some(Popup::new(PopupType::ErrorSave, err.to_string()));
+            }
+        }
+    }
+
+    /// Asks where to write the open save, which becomes the file Save uses
+    fn save_file_as(&mut self) {
+        let Some(save) = &self.save else {
+            self.popup = Some(Popup::new(
+                PopupType::ErrorSave,
+                "Error: No Save Loaded".to_string(),
+            ));
+            return;
+        };
+
+        match save.to_lua_data() {
+            Ok(x) => {
+                let task = rfd::AsyncFileDialog::new().save_file();
+                let path_sender = self.path_channel.0.clone();
+                let popup_sender = self.popup_channel.0.clone();
+                let backup_dir = self.config.backup_dir();
+                let keep = self.config.backups_to_keep;
+                execute(async move {
+                    let Some(file) = task.await else {
+                        return;
+                    };
+                    match backup::write_with_backup(
+                        file.path(),
+                        &x,
+                        &backup_dir,
+                        keep,
+                    ) {
+                        Ok(_) => {
+                            let _ = path_sender
+                                .send((file.path().to_path_buf(), x));
+                        }
+                        Err(err) => {
+                            popup_sender
+                                .send(Popup::new(
+                                    PopupType::ErrorSave,
+                                    err.to_string(),
+                                ))
+                                .unwrap();
+                        }
+                    }
+                });
+            }
+            Err(err) => {
+                self.popup =
+                    Some(Popup::new(PopupType::ErrorSave, err.to_string()));
+            }
+        }
+    }
+
+    /// Asks for a meta file to merge into the open one
+    fn pick_merge(&mut self) {
+        let merge_sender = self.merge_channel.0.clone();
+        let task = rfd::AsyncFileDialog::new().pick_file();
+        execute(async move {
+            if let Some(file) = task.await {
+                let data = file.read().await;
+                let _ = merge_sender.send((file.path().to_path_buf(), data));
+            }
+        });
+    }
+
+    /// Works out what merging a meta file would change, and asks the user
+    /// to confirm it
+    fn prepare_merge(&mut self, data: Vec<u8>) {
+        let Some(SaveType::Meta(meta)) = &self.save else {
+            return;
+        };
+        let theirs = match Meta::from_lua_table(data) {
+            Ok(theirs) => theirs,
+            Err(err) => {
+                self.popup = Some(Popup::new(
+                    PopupType::ErrorLoad,
+                    load_error_text(&err),
+                ));
+                return;
+            }
+        };
+        let changes = meta.merge_changes(&theirs, self.merge_strategy);
+        self.confirm_changes(
+            changes,
+            "Nothing to merge, the open meta already has everything",
+        );
+    }
+
+    /// Asks the user to confirm changes to the open meta, listing them
+    fn confirm_changes(&mut self, changes: MetaEdit, unchanged: &str) {
+        let summary = if changes.is_empty() {
+            unchanged.to_string()
+        } else {
+            changes
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        self.pending_changes = Some(changes);
+        self.popup = Some(Popup::new(PopupType::ConfirmChanges, summary));
+    }
+
+    /// Works out what a preset changes in the open meta, and asks the user
+    /// to confirm it
+    fn prepare_preset(&mut self, preset: &Preset) {
+        let Some(SaveType::Meta(meta)) = &self.save else {
+            return;
+        };
+        let changes = preset.changes(meta);
+        self.confirm_changes(
+            changes,
+            "Nothing to change, the open meta already matches the preset",
+        );
+    }
+
+    /// Saves the open meta as a preset, under the name the user typed
+    fn save_preset(&mut self) {
+        let Some(SaveType::Meta(meta)) = &self.save else {
+            return;
+        };
+        let Some(dir) = presets::presets_dir() else {
+            self.popup = Some(Popup::new(
+                PopupType::ErrorSave,
+                "Could not find a config directory".to_string(),
+            ));
+            return;
+        };
+        let preset = Preset::capture(self.preset_name.trim(), meta);
+        match preset.save(&dir) {
+            Ok(_) => {
+                self.presets = presets::list_presets(&dir);
+                self.preset_name.clear();
+            }
+            Err(err) => {
+                self.popup =
+                    Some(Popup::new(PopupType::ErrorSave, err.to_string()));
+            }
+        }
+    }
+
+    /// Deletes a saved preset
+    fn delete_preset(&mut self, preset: &Preset) {
+        let Some(dir) = presets::presets_dir() else {
+            return;
+        };
+        if let Err(err) = preset.delete(&dir) {
+            self.popup =
+                Some(Popup::new(PopupType::ErrorSave, err.to_string()));
+        }
+        self.presets = presets::list_presets(&dir);
+    }
+
+    /// Asks where to export the open meta, in the chosen format
+    fn export_meta(&mut self) {
+        let Some(SaveType::Meta(meta)) = &self.save else {
+            return;
+        };
+        let text = match meta.export(self.export_format) {
+            Ok(text) => text,
+            Err(err) => {
+                self.popup =
+                    Some(Popup::new(PopupType::ErrorSave, err.to_string()));
+                return;
+            }
+        };
+        let extension = self.export_format.extension();
+        let task = rfd::AsyncFileDialog::new()
+            .add_filter(self.export_format.to_string(), &[extension])
+            .set_file_name(format!("meta.{}", extension))
+            .save_file();
+        let popup_sender = self.popup_channel.0.clone();
+        execute(async move {
+            if let Some(file) = task.await
+                && let Err(err) = file.write(text.as_bytes()).await
+            {
+                popup_sender
+                    .send(Popup::new(PopupType::ErrorSave, err.to_string()))
+                    .unwrap();
+            }
+        });
+    }
+
+    /// Asks for an exported meta file to import
+    fn pick_import(&mut self) {
+        let import_sender = self.import_channel.0.clone();
+        let task = rfd::AsyncFileDialog::new()
+            .add_filter("Meta Export", &["json", "toml", "csv"])
+            .pick_file();
+        execute(async move {
+            if let Some(file) = task.await {
+                let data = file.read().await;
+                let _ = import_sender.send((file.path().to_path_buf(), data));
+            }
+        });
+    }
+
+    /// Imports an exported meta file in place of the open meta
+    /// The import is one undo step if a meta is open
+    fn import_meta(&mut self, path: PathBuf, data: Vec<u8>) {
+        let format =
+            ExportFormat::from_path(&path).unwrap_or(self.export_format);
+        // The import is checked against the items of the open meta's version
+        let version = match &self.save {
+            Some(SaveType::Meta(meta)) => meta.version().map(str::to_string),
+            _ => None,
+        };
+        let result = String::from_utf8(data)
+            .map_err(SaveError::Encoding)
+            .and_then(|text| {
+                Meta::import_for(&text, format, self.import_unknown, version)
+            });
+        match (result, self.save.as_mut()) {
+            (Ok(imported), Some(SaveType::Meta(meta))) => {
+                let changes = meta.diff(&imported);
+                meta.apply(&changes);
+            }
+            (Ok(imported), _) => {
+                self.set_save(SaveType::Meta(imported), None);
+                self.tab = TabState::Editor;
+            }
+            (Err(err), _) => {
+                self.popup =
+                    Some(Popup::new(PopupType::ErrorLoad, err.to_string()));
+            }
+        }
+    }
+
+    /// Asks for a file to compare the open save with
+    fn pick_comparison(&mut self) {
+        let compare_sender = self.compare_channel.0.clone();
+        let task = rfd::AsyncFileDialog::new().pick_file();
+        execute(async move {
+            if let Some(file) = task.await {
+                let data = file.read().await;
+                let _ = compare_sender.send((file.path().to_path_buf(), data));
+            }
+        });
+    }
+
+    /// Compares the open save with what is in another file
+    fn compare(&mut self, other: PathBuf, data: Vec<u8>) {
+        let Some(save) = &self.save else {
+            return;
+        };
+        let result = save.to_lua_data().and_then(|mine| {
+            let mine = SaveValue::from_lua_data(&mine)?;
+            let theirs = SaveValue::from_lua_data(&data)?;
+            Diff::new(&mine, &theirs, false)
+        });
+        match result {
+            Ok(diff) => self.comparison = Some((other, diff)),
+            Err(err) => {
+                self.popup = Some(Popup::new(
+                    PopupType::ErrorLoad,
+                    load_error_text(&err),
+                ));
+            }
+        }
+    }
 
-                let _ = meta_sender.send(SaveType::Meta(meta.unwrap()));
-                ctx.request_repaint();
+    /// Replaces the open save, remembering the file it is from
+    fn set_save(&mut self, save: SaveType, source: Option<PathBuf>) {
+        self.clean = save.to_lua_data().ok();
+        self.watcher = source.as_deref().map(FileWatcher::new);
+        self.source = source;
+        self.save = Some(save);
+        self.pending_reload = None;
+        self.comparison = None;
+    }
+
+    /// Checks if the open save was edited since it was loaded or saved
+    fn has_edits(&self) -> bool {
+        match (&self.save, &self.clean) {
+            (Some(save), Some(clean)) => {
+                save.to_lua_data().ok().as_ref() != Some(clean)
             }
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Reloads the open file if the game wrote it
+    /// With no local edits the file is reloaded right away, otherwise the
+    /// user chooses what to keep
+    fn check_watcher(&mut self, ctx: &Context) {
+        // Changes wait for any open popup, so they never replace it
+        if !self.config.watch_files || self.popup.is_some() {
+            return;
+        }
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        ctx.request_repaint_after(POLL_INTERVAL);
+        if !watcher.poll() {
+            return;
+        }
+
+        // The game may still be writing the file, in which case it is read
+        // again when the write finishes and changes the file once more
+        let path = watcher.path().to_path_buf();
+        let Ok(mut theirs) = SaveType::from_file(&path) else {
+            return;
+        };
+        theirs.find_version(&path);
+        if self.has_edits() {
+            self.pending_reload = Some(theirs);
+            self.popup = Some(Popup::new(
+                PopupType::ConfirmReload,
+                format!(
+                    "{} was changed by the game, but you have edits that are not saved.",
+                    path.display()
+                ),
+            ));
+        } else {
+            self.clean = theirs.to_lua_data().ok();
+            self.save = Some(theirs);
+        }
+    }
+
+    /// Undoes with Ctrl+Z and redoes with Ctrl+Shift+Z
+    /// A text box that has focus keeps the keys for its own undo
+    fn handle_shortcuts(&mut self, ctx: &Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let Some(SaveType::Meta(meta)) = self.save.as_mut() else {
+            return;
+        };
+        // Shift is checked first, as Ctrl+Z also matches with Shift held
+        let (redo, undo) = ctx.input_mut(|input| {
+            (
+                input.consume_shortcut(&KeyboardShortcut::new(
+                    Modifiers::COMMAND | Modifiers::SHIFT,
+                    Key::Z,
+                )),
+                input.consume_shortcut(&KeyboardShortcut::new(
+                    Modifiers::COMMAND,
+                    Key::Z,
+                )),
+            )
         });
+        if redo {
+            meta.redo();
+        } else if undo {
+            meta.undo();
+        }
     }
+
+    /// Shows the file being edited in the window title
+    fn update_title(&mut self, ctx: &Context) {
+        let title = match (&self.source, &self.save) {
+            (Some(path), _) => {
+                format!("{} - {}", WINDOW_TITLE, path.display())
+            }
+            (None, Some(_)) => format!("{} - Unsaved", WINDOW_TITLE),
+            (None, None) => WINDOW_TITLE.to_string(),
+        };
+        if title != self.title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.title = title;
+        }
+    }
+
     #[cfg(feature = "dev")]
     fn make_dev(&mut self) {
         let dev_sender = self.dev.data_channel.0.clone();
@@ -126,32 +626,13 @@ impl MyApp {
                 }
             }
             PopupType::ErrorLoad => {
-                let mut error_text = popup_text.clone();
-                match popup_text.as_str() {
-                    "corrupt deflate stream" => {
-                        error_text = "Make sure the file you selected is a valid Balatro save file".to_string();
-                    }
-                    _ if popup_text
-                        .contains("stream did not contain valid UTF-8") =>
-                    {
-                        error_text = "Make sure the file you selected is a valid Balatro save file".to_string();
-                    }
-
-                    _ if popup_text.contains("runtime error: Subtable") => {
-                        error_text =
-                            "Make sure the file you selected is a Meta file"
-                                .to_string();
-                    }
-
-                    _ => {} // Leave error text as is
-                }
                 let modal = egui::Modal::new(egui::Id::new(
                     "Error Loading File",
                 ))
                 .show(ctx, |ui| {
                     ui.label("Error Loading File");
                     ui.horizontal(|ui| {
-                        ui.label(error_text);
+                        ui.label(popup_text);
 
                         if ui.button("Close").clicked() {
                             self.popup = None;
@@ -170,7 +651,7 @@ impl MyApp {
                     ui.label("Are you sure you want to load the default meta? This will overwrite your current meta.");
                     ui.horizontal(|ui| {
                         if ui.button("Yes").clicked() {
-                            self.save = Some(SaveType::Meta(Meta::from_defaults()));
+                            self.set_save(SaveType::Meta(Meta::from_defaults()), None);
                             self.tab = TabState::Editor;
                             self.popup = None;
                         }
@@ -183,22 +664,96 @@ impl MyApp {
                     self.popup = None;
                 }
             }
-            PopupType::ConfirmMetaFile => {
+            PopupType::ConfirmReload => {
+                let modal = egui::Modal::new(egui::Id::new("Confirm Reload"))
+                    .show(ctx, |ui| {
+                        ui.label(popup_text);
+                        ui.horizontal(|ui| {
+                            if ui.button("Keep mine").clicked() {
+                                self.pending_reload = None;
+                                self.popup = None;
+                            }
+                            if ui.button("Take the game's").clicked() {
+                                if let Some(theirs) = self.pending_reload.take()
+                                {
+                                    self.clean = theirs.to_lua_data().ok();
+                                    self.save = Some(theirs);
+                                }
+                                self.popup = None;
+                            }
+                            // Only meta files have unlock flags to merge
+                            if let (
+                                Some(SaveType::Meta(mine)),
+                                Some(SaveType::Meta(theirs)),
+                            ) = (&mut self.save, &self.pending_reload)
+                                && ui.button("Merge unlocks").clicked()
+                            {
+                                mine.merge(theirs, MergeStrategy::Union);
+                                self.pending_reload = None;
+                                self.popup = None;
+                            }
+                        });
+                    });
+                if modal.should_close() {
+                    self.pending_reload = None;
+                    self.popup = None;
+                }
+            }
+            PopupType::ConfirmChanges => {
+                let modal = egui::Modal::new(egui::Id::new("Confirm Changes"))
+                    .show(ctx, |ui| {
+                        ui.label("This changes these items:");
+                        egui::ScrollArea::vertical().max_height(300.0).show(
+                            ui,
+                            |ui| {
+                                ui.label(popup_text);
+                            },
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("Apply").clicked() {
+                                if let (
+                                    Some(SaveType::Meta(meta)),
+                                    Some(changes),
+                                ) = (
+                                    &mut self.save,
+                                    self.pending_changes.take(),
+                                ) {
+                                    meta.apply(&changes);
+                                }
+                                self.popup = None;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.pending_changes = None;
+                                self.popup = None;
+                            }
+                        });
+                    });
+                if modal.should_close() {
+                    self.pending_changes = None;
+                    self.popup = None;
+                }
+            }
+            PopupType::ConfirmOpenFile => {
                 let modal = egui::Modal::new(egui::Id::new(
-                    "Confirm Meta Overwrite",)).show(ctx, |ui| {
-                    ui.label("Are you sure you want to load another meta file? This will overwrite your current meta.");
+                    "Confirm File Overwrite",)).show(ctx, |ui| {
+                    ui.label("Are you sure you want to load another file? This will overwrite your current save.");
                     ui.horizontal(|ui| {
                         if ui.button("Yes").clicked() {
                             self.popup = None;
-                            self.make_meta(ui);
+                            match self.pending_open.take() {
+                                Some(path) => self.open_path(path),
+                                None => self.open_file(ui),
+                            }
                         }
                         if ui.button("No").clicked() {
                             self.popup = None;
+                            self.pending_open = None;
                         }
                     })
             });
                 if modal.should_close() {
                     self.popup = None;
+                    self.pending_open = None;
                 }
             }
         }
@@ -209,10 +764,24 @@ impl eframe::App for MyApp {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         // assign sample text once it comes in
 
-        if let Ok(save) = self.save_channel.1.try_recv() {
-            self.save = Some(save);
+        if let Ok((save, path)) = self.save_channel.1.try_recv() {
+            self.set_save(save, Some(path));
             self.tab = TabState::Editor;
         }
+        if let Ok((path, data)) = self.import_channel.1.try_recv() {
+            self.import_meta(path, data);
+        }
+        if let Ok((_, data)) = self.merge_channel.1.try_recv() {
+            self.prepare_merge(data);
+        }
+        if let Ok((path, data)) = self.compare_channel.1.try_recv() {
+            self.compare(path, data);
+        }
+        if let Ok((path, data)) = self.path_channel.1.try_recv() {
+            self.watcher = Some(FileWatcher::new(&path));
+            self.source = Some(path);
+            self.clean = Some(data);
+        }
         if let Ok(popup) = self.popup_channel.1.try_recv() {
             self.popup = Some(popup);
         }
@@ -222,7 +791,10 @@ impl eframe::App for MyApp {
             self.dev.table = None;
         }
 
+        self.check_watcher(ctx);
+        self.handle_shortcuts(ctx);
         self.handle_popops(ctx);
+        self.update_title(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -275,12 +847,12 @@ impl eframe::App for MyApp {
 
             match self.tab {
                 TabState::None => {
-                    if ui.button("📂 Open Meta file").clicked() {
+                    if ui.button("📂 Open file").clicked() {
                         if self.save.is_none() {
-                            self.make_meta(ui);
+                            self.open_file(ui);
                         } else {
                             self.popup = Some(Popup::new(
-                                PopupType::ConfirmMetaFile,
+                                PopupType::ConfirmOpenFile,
                                 "".to_string(),
                             ));
                         }
@@ -294,76 +866,21 @@ impl eframe::App for MyApp {
                         }
                     }
 
-                    if ui.button("💾 Save Editor to File").clicked() {
-                        if self.save.is_none() {
-                            self.popup = Some(Popup::new(
-                                PopupType::ErrorSave,
-                                "Error: No Save Loaded".to_string(),
-                            ));
-                            return;
+                    ui.horizontal(|ui| {
+                        if ui.button("💾 Save").clicked() {
+                            self.save_file();
                         }
-
-                        let save = self.save.as_mut().unwrap();
-
-                        match save {
-                            SaveType::Meta(meta) => {
-                                let lua_context = LuaContext::new();
-                                let x = meta.to_lua_data(&lua_context);
-                                match x {
-                                    Ok(x) => {
-                                        let task = rfd::AsyncFileDialog::new()
-                                            .save_file();
-                                        execute(async move {
-                                            let file = task.await;
-                                            if let Some(file) = file {
-                                                // let file_content = self.meta.to_lua_data();
-                                                _ = file.write(&x).await;
-                                            }
-                                        });
-                                    }
-                                    Err(err) => {
-                                        self.popup = Some(Popup::new(
-                                            PopupType::ErrorSave,
-                                            err.to_string(),
-                                        ));
-                                    }
-                                }
-                            }
-
-                            SaveType::Profile(profile) => {
-                                let lua_context = LuaContext::new();
-                                let x = profile.to_lua_data(&lua_context);
-                                match x {
-                                    Ok(x) => {
-                                        let task = rfd::AsyncFileDialog::new()
-                                            .save_file();
-                                        execute(async move {
-                                            let file = task.await;
-                                            if let Some(file) = file {
-                                                // let file_content = self.meta.to_lua_data();
-                                                _ = file.write(&x).await;
-                                            }
-                                        });
-                                    }
-                                    Err(err) => {
-                                        self.popup = Some(Popup::new(
-                                            PopupType::ErrorSave,
-                                            err.to_string(),
-                                        ));
-                                    }
-                                }
-                            }
+                        if ui.button("💾 Save As").clicked() {
+                            self.save_file_as();
                         }
-
-
-
-
-
+                    });
+                    if let Some(source) = &self.source {
+                        ui.label(format!("Editing {}", source.display()));
                     }
 
                     if ui.button("❓ Default Meta").clicked() {
                         if self.save.is_none() {
-                            self.save = Some(SaveType::Meta(Meta::from_defaults()));
+                            self.set_save(SaveType::Meta(Meta::from_defaults()), None);
                             self.tab = TabState::Editor;
                         } else {
                             self.popup = Some(Popup::new(
@@ -372,6 +889,171 @@ impl eframe::App for MyApp {
                             ));
                         }
                     }
+
+                    ui.separator();
+                    if ui.button("🔍 Find Saves").clicked() {
+                        self.locations = locator::find_save_locations();
+                    }
+                    if let Some(path) =
+                        drawings::draw_locations(&self.locations, ui)
+                    {
+                        if self.save.is_none() {
+                            self.open_path(path);
+                        } else {
+                            self.pending_open = Some(path);
+                            self.popup = Some(Popup::new(
+                                PopupType::ConfirmOpenFile,
+                                "".to_string(),
+                            ));
+                        }
+                    }
+
+                    ui.separator();
+                    if ui
+                        .add_enabled(
+                            self.save.is_some(),
+                            egui::Button::new("🔀 Compare with File"),
+                        )
+                        .clicked()
+                    {
+                        self.pick_comparison();
+                    }
+                    ui.horizontal(|ui| {
+                        let is_meta =
+                            matches!(self.save, Some(SaveType::Meta(_)));
+                        if ui
+                            .add_enabled(
+                                is_meta,
+                                egui::Button::new("🔗 Merge Meta File"),
+                            )
+                            .clicked()
+                        {
+                            self.pick_merge();
+                        }
+                        egui::ComboBox::from_id_salt("Merge Strategy")
+                            .selected_text(self.merge_strategy.to_string())
+                            .show_ui(ui, |ui| {
+                                for strategy in MergeStrategy::ALL {
+                                    ui.selectable_value(
+                                        &mut self.merge_strategy,
+                                        strategy,
+                                        strategy.to_string(),
+                                    );
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        let is_meta =
+                            matches!(self.save, Some(SaveType::Meta(_)));
+                        if ui
+                            .add_enabled(
+                                is_meta,
+                                egui::Button::new("📤 Export Meta"),
+                            )
+                            .clicked()
+                        {
+                            self.export_meta();
+                        }
+                        if ui.button("📥 Import Meta").clicked() {
+                            self.pick_import();
+                        }
+                        egui::ComboBox::from_id_salt("Export Format")
+                            .selected_text(self.export_format.to_string())
+                            .show_ui(ui, |ui| {
+                                for format in ExportFormat::ALL {
+                                    ui.selectable_value(
+                                        &mut self.export_format,
+                                        format,
+                                        format.to_string(),
+                                    );
+                                }
+                            });
+                        ui.checkbox(
+                            &mut self.import_unknown,
+                            "Allow modded items",
+                        );
+                    });
+                    if let Some((other, diff)) = &self.comparison {
+                        drawings::draw_diff(other, diff, ui);
+                    }
+
+                    ui.separator();
+                    ui.label("Presets");
+                    let is_meta = matches!(self.save, Some(SaveType::Meta(_)));
+                    let mut apply = None;
+                    let mut delete = None;
+                    for preset in Preset::builtin() {
+                        if ui
+                            .add_enabled(
+                                is_meta,
+                                egui::Button::new(&preset.name),
+                            )
+                            .clicked()
+                        {
+                            apply = Some(preset);
+                        }
+                    }
+                    for preset in self.presets.iter() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    is_meta,
+                                    egui::Button::new(&preset.name),
+                                )
+                                .clicked()
+                            {
+                                apply = Some(preset.clone());
+                            }
+                            if ui.button("🗑 Delete").clicked() {
+                                delete = Some(preset.clone());
+                            }
+                        });
+                    }
+                    if let Some(preset) = apply {
+                        self.prepare_preset(&preset);
+                    }
+                    if let Some(preset) = delete {
+                        self.delete_preset(&preset);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.preset_name);
+                        let named = !self.preset_name.trim().is_empty();
+                        if ui
+                            .add_enabled(
+                                is_meta && named,
+                                egui::Button::new("💾 Save as Preset"),
+                            )
+                            .clicked()
+                        {
+                            self.save_preset();
+                        }
+                    });
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Backups");
+                        if ui.button("🔄 Refresh").clicked() {
+                            self.backups.refresh(&self.config.backup_dir());
+                        }
+                    });
+                    if let Some(restore) =
+                        drawings::draw_backups(&mut self.backups, ui)
+                    {
+                        let backup_dir = self.config.backup_dir();
+                        match backup::restore(
+                            &restore,
+                            &backup_dir,
+                            self.config.backups_to_keep,
+                        ) {
+                            Ok(()) => self.backups.refresh(&backup_dir),
+                            Err(err) => {
+                                self.popup = Some(Popup::new(
+                                    PopupType::ErrorSave,
+                                    err.to_string(),
+                                ));
+                            }
+                        }
+                    }
                 }
 
                 TabState::Editor => {
@@ -390,6 +1072,18 @@ impl eframe::App for MyApp {
                             SaveType::Profile(profile) => {
                                 drawings::draw_profile(profile, ctx, ui);
                             }
+
+                            SaveType::Save(save) => {
+                                drawings::draw_savegame(save, ui);
+                            }
+
+                            SaveType::Settings(settings) => {
+                                drawings::draw_settings(settings, ui);
+                            }
+
+                            SaveType::Generic(value) => {
+                                drawings::draw_tree(value, ui);
+                            }
                         }
                     });
                 }
@@ -397,8 +1091,39 @@ impl eframe::App for MyApp {
                 TabState::Settings => {
                     ui.label("Settings");
                     ui.horizontal(|ui| {
-                        ui.label("No Settings yet");
+                        ui.label("Backups to keep of each file");
+                        let changed = ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut self.config.backups_to_keep,
+                                )
+                                .range(0..=100),
+                            )
+                            .changed();
+                        if changed && let Err(err) = self.config.save() {
+                            self.popup = Some(Popup::new(
+                                PopupType::ErrorSave,
+                                err.to_string(),
+                            ));
+                        }
                     });
+                    ui.label(format!(
+                        "Backups are kept in {}",
+                        self.config.backup_dir().display()
+                    ));
+                    if ui
+                        .checkbox(
+                            &mut self.config.watch_files,
+                            "Reload the open file when the game changes it",
+                        )
+                        .changed()
+                        && let Err(err) = self.config.save()
+                    {
+                        self.popup = Some(Popup::new(
+                            PopupType::ErrorSave,
+                            err.to_string(),
+                        ));
+                    }
                 }
 
                 TabState::Help => {
@@ -436,6 +1161,35 @@ impl eframe::App for MyApp {
 //     Ok(s)
 // }
 
+/// Turns an error from loading a file into the text shown to the user
+fn load_error_text(err: &SaveError) -> String {
+    match err {
+        SaveError::Decompress(_)
+        | SaveError::Encoding(_)
+        | SaveError::MissingSubtable(_) => {
+            "Make sure the file you selected is a valid Balatro save file"
+                .to_string()
+        }
+        SaveError::WrongFileType { expected, .. } => {
+            format!("Make sure the file you selected is a {} file", expected)
+        }
+        _ => err.to_string(),
+    }
+}
+
+/// Makes the popup listing the items files that could not be read, if any
+fn skipped_items_popup() -> Option<Popup> {
+    let skipped = registries().skipped();
+    if skipped.is_empty() {
+        return None;
+    }
+    let mut message = String::from("These items files were skipped:");
+    for (path, err) in skipped {
+        message.push_str(&format!("\n{}: {}", path.display(), err));
+    }
+    Some(Popup::new(PopupType::ErrorLoad, message))
+}
+
 fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
     // this is stupid... use any executor of your choice instead
     std::thread::spawn(move || futures::executor::block_on(f));
@@ -1,10 +1,22 @@
+mod draw_backups;
 #[cfg(feature = "dev")]
 mod draw_dev;
+mod draw_diff;
+mod draw_locations;
 mod draw_meta;
 mod draw_profile;
+mod draw_savegame;
+mod draw_settings;
+mod draw_tree;
 
+pub use draw_backups::{BackupBrowser, draw_backups};
 #[cfg(feature = "dev")]
 pub use draw_dev::draw_dev;
+pub use draw_diff::draw_diff;
+pub use draw_locations::draw_locations;
 pub use draw_meta::draw_meta;
 
 pub use draw_profile::draw_profile;
+pub use draw_savegame::draw_savegame;
+pub use draw_settings::draw_settings;
+pub use draw_tree::draw_tree;
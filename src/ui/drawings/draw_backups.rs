@@ -0,0 +1,86 @@
+use crate::backup::{self, Backup};
+use crate::enums::SaveType;
+
+use std::path::Path;
+
+/// What the backup browser has listed and selected
+#[derive(Default)]
+pub struct BackupBrowser {
+    /// The backups in the backup directory, newest first
+    pub backups: Vec<Backup>,
+    /// The index of the backup being previewed
+    pub selected: Option<usize>,
+    /// The summary of the selected backup
+    pub preview: String,
+}
+
+impl BackupBrowser {
+    /// Lists the backups in a directory
+    pub fn new(backup_dir: &Path) -> Self {
+        let mut browser = BackupBrowser::default();
+        browser.refresh(backup_dir);
+        browser
+    }
+
+    /// Lists the backups in a directory again, clearing the selection
+    pub fn refresh(&mut self, backup_dir: &Path) {
+        self.backups = backup::list_backups(backup_dir);
+        self.selected = None;
+        self.preview.clear();
+    }
+
+    /// Selects a backup and reads it for the preview
+    fn select(&mut self, index: usize) {
+        self.selected = Some(index);
+        self.preview = match self.backups[index]
+            .read()
+            .and_then(SaveType::from_lua_data)
+        {
+            Ok(save) => save.summary(),
+            Err(err) => format!("Could not read the backup: {}", err),
+        };
+    }
+}
+
+/// Draws the list of backups and a preview of the selected one
+/// Returns the backup to restore if its button was clicked
+pub fn draw_backups(
+    browser: &mut BackupBrowser,
+    ui: &mut egui::Ui,
+) -> Option<Backup> {
+    if browser.backups.is_empty() {
+        ui.label("No backups yet");
+        return None;
+    }
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical().id_salt("backups").max_height(200.0).show(
+        ui,
+        |ui| {
+            for (index, backup) in browser.backups.iter().enumerate() {
+                let label = format!(
+                    "{}  {}",
+                    backup.created_label(),
+                    backup.original.display()
+                );
+                if ui
+                    .selectable_label(browser.selected == Some(index), label)
+                    .clicked()
+                {
+                    clicked = Some(index);
+                }
+            }
+        },
+    );
+    if let Some(index) = clicked {
+        browser.select(index);
+    }
+
+    let selected = browser.selected?;
+    ui.separator();
+    ui.label(&browser.preview);
+    let backup = &browser.backups[selected];
+    ui.button(format!("⟲ Restore to {}", backup.original.display()))
+        .clicked()
+        .then(|| backup.clone())
+}
@@ -69,8 +69,8 @@ pub fn draw_dev(app: &mut MyApp, ui: &mut egui::Ui) {
                         if let Err(e) = run_result {
                             app.dev.output = format!("Error: {}", e);
                         } else if let Ok(output) =
-                            lua.lua.globals().get("Output")
-                            && output != "".to_string()
+                            lua.lua.globals().get::<String>("Output")
+                            && !output.is_empty()
                         {
                             app.dev.output = output;
                         } else {
@@ -79,14 +79,14 @@ pub fn draw_dev(app: &mut MyApp, ui: &mut egui::Ui) {
                         println!("{}", app.dev.output);
                     }
 
-                    if ui.button("Print Table").clicked() {
-                        if let Some(table) = &app.dev.table {
-                            lua.lua.globals().set("dev_table", table).unwrap();
-                            lua.lua
-                                .load("print_nested_table(dev_table)")
-                                .exec()
-                                .unwrap();
-                        }
+                    if ui.button("Print Table").clicked()
+                        && let Some(table) = &app.dev.table
+                    {
+                        lua.lua.globals().set("dev_table", table).unwrap();
+                        lua.lua
+                            .load("print_nested_table(dev_table)")
+                            .exec()
+                            .unwrap();
                     }
                     if ui.button("Clear").clicked() {
                         print!("\n\n\n\n\n\n\n\n\n\n\n");
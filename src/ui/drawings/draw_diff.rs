@@ -0,0 +1,27 @@
+use crate::diff::Diff;
+
+use std::path::Path;
+
+/// Draws the differences between the open save and another file
+/// Added lines are green, removed ones red and changed ones yellow
+pub fn draw_diff(other: &Path, diff: &Diff, ui: &mut egui::Ui) {
+    ui.label(format!("Compared with {}", other.display()));
+    if diff.is_empty() {
+        ui.label("The files are the same");
+        return;
+    }
+
+    egui::ScrollArea::vertical().id_salt("diff").max_height(300.0).show(
+        ui,
+        |ui| {
+            for line in diff.lines() {
+                let colour = match line.chars().next() {
+                    Some('+') => egui::Color32::GREEN,
+                    Some('-') => egui::Color32::LIGHT_RED,
+                    _ => egui::Color32::YELLOW,
+                };
+                ui.label(egui::RichText::new(line).color(colour).monospace());
+            }
+        },
+    );
+}
@@ -0,0 +1,46 @@
+use crate::locator::SaveLocation;
+
+use std::path::PathBuf;
+
+/// Draws the save directories the locator found, with a button per file
+/// Returns the file whose button was clicked, if any
+pub fn draw_locations(
+    locations: &[SaveLocation],
+    ui: &mut egui::Ui,
+) -> Option<PathBuf> {
+    let mut clicked = None;
+
+    if locations.is_empty() {
+        ui.label("No Balatro saves found");
+        return None;
+    }
+
+    for location in locations {
+        ui.label(location.root.display().to_string());
+        ui.indent(location.root.display().to_string(), |ui| {
+            if let Some(settings) = &location.settings
+                && ui.button("⚙ Settings").clicked()
+            {
+                clicked = Some(settings.clone());
+            }
+            for slot in location.slots.iter() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Profile {}", slot.number));
+                    let files = [
+                        ("Meta", &slot.meta),
+                        ("Profile", &slot.profile),
+                        ("Save", &slot.save),
+                    ];
+                    for (label, file) in files {
+                        if let Some(file) = file
+                            && ui.button(label).clicked()
+                        {
+                            clicked = Some(file.clone());
+                        }
+                    }
+                });
+            }
+        });
+    }
+    clicked
+}
@@ -1,6 +1,8 @@
-use crate::saves::Meta;
+use crate::enums::ItemCategory;
+use crate::saves::{ItemKey, Meta, mod_name};
 
 use egui::{Context, Label};
+use std::collections::BTreeMap;
 
 /// This is used to give the to_title_case method on str
 use inflector::Inflector;
@@ -8,12 +10,93 @@ use inflector::Inflector;
 /// This is used to allow us to use the fuzzy_match method on FuzzyMatcher
 use fuzzy_matcher::FuzzyMatcher;
 
+/// The categories the Misc section lists
+const MISC_CATEGORIES: [ItemCategory; 5] = [
+    ItemCategory::Deck,
+    ItemCategory::Edition,
+    ItemCategory::Tag,
+    ItemCategory::Booster,
+    ItemCategory::Blind,
+];
+
+/// The bulk edits a section offers besides Unlock All
+type BulkEdit = (&'static str, fn(&mut Meta, ItemCategory));
+const BULK_EDITS: [BulkEdit; 4] = [
+    ("Lock All", Meta::lock_category),
+    ("Reset to Default", Meta::reset_category),
+    ("Discover All", Meta::discover_category),
+    ("Clear Alerts", Meta::clear_alerts),
+];
+
+/// The bulk edits each mod offers, for all its items or one category
+type ModEdit = (&'static str, fn(&mut Meta, &str, Option<ItemCategory>));
+const MOD_EDITS: [ModEdit; 2] =
+    [("Unlock", Meta::unlock_mod), ("Lock", Meta::lock_mod)];
+
 pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
     let window_size = ctx.screen_rect().size();
     let num_columns = 2;
     let scroll_height = window_size.y * 0.4;
     let search_width = window_size.x / num_columns as f32 * 0.65;
 
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(meta.can_undo(), egui::Button::new("↶ Undo"))
+            .clicked()
+        {
+            meta.undo();
+        }
+        if ui
+            .add_enabled(meta.can_redo(), egui::Button::new("↷ Redo"))
+            .clicked()
+        {
+            meta.redo();
+        }
+        match meta.version() {
+            Some(version) => ui.label(format!("Game version {}", version)),
+            None => ui.label("Game version unknown"),
+        };
+    });
+    if let Some(warning) = meta.version_warning() {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!("⚠ Warning: {}", warning),
+        );
+    }
+
+    egui::CollapsingHeader::new("Completion").default_open(true).show(
+        ui,
+        |ui| {
+            egui::Grid::new("Completion Grid").striped(true).show(ui, |ui| {
+                for stats in meta.stats() {
+                    ui.label(stats.category.to_string());
+                    let flags = [
+                        ("unlocked", stats.unlocked),
+                        ("discovered", stats.discovered),
+                        ("alerted", stats.alerted),
+                    ];
+                    for (flag, count) in flags {
+                        if count.total == 0 {
+                            ui.label("");
+                            continue;
+                        }
+                        ui.add(
+                            egui::ProgressBar::new(count.fraction())
+                                .desired_width(160.0)
+                                .text(format!(
+                                    "{}/{} {}",
+                                    count.done, count.total, flag
+                                )),
+                        );
+                    }
+                    ui.end_row();
+                }
+            });
+        },
+    );
+
+    draw_mods(meta, ui);
+
     ui.columns(num_columns, |columns| {
         columns[0].with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
             ui.add(Label::new(
@@ -26,8 +109,9 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                         .hint_text("Filter Jokers"),
                 );
                 if ui.button("Unlock All").clicked() {
-                    meta.unlock_all_type("j_");
+                    meta.record(|meta| meta.unlock_all_type("j_"));
                 }
+                draw_bulk_menu(meta, &[ItemCategory::Joker], ui);
             });
 
             egui::containers::ScrollArea::both()
@@ -38,7 +122,7 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                 .show(ui, |ui| {
                     let mut joker_names = meta.get_joker_names();
 
-                    if meta.filters.joker != "" {
+                    if !meta.filters.joker.is_empty() {
                         joker_names.retain(|name| {
                             let score = meta.matcher.fuzzy_match(
                                 &name[2..].to_lowercase(),
@@ -47,29 +131,10 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                             if score.is_some_and(|x| x > 1) {
                                 return true;
                             }
-                            return false;
-                        });
-                    }
-                    for joker_name in joker_names.iter() {
-                        ui.horizontal(|ui| {
-                            ui.label(format!("{}", &joker_name[2..].to_title_case()));
-                            let joker = meta.get_item(&joker_name);
-
-                            let joker_val = joker.unwrap();
-
-                            if joker_val.can_be_alerted() {
-                                ui.checkbox(&mut joker_val.alerted, "Alerted");
-                            }
-
-                            if joker_val.can_be_discovered() {
-                                ui.checkbox(&mut joker_val.discovered, "Discovered");
-                            }
-
-                            if joker_val.can_be_unlocked() {
-                                ui.checkbox(&mut joker_val.unlocked, "Unlocked");
-                            }
+                            false
                         });
                     }
+                    draw_items(meta, &joker_names, "Jokers", ui);
                 });
 
             ui.separator();
@@ -86,8 +151,9 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                         .hint_text("Filter Cards"),
                 );
                 if ui.button("Unlock All").clicked() {
-                    meta.unlock_all_type("c_");
+                    meta.record(|meta| meta.unlock_all_type("c_"));
                 }
+                draw_bulk_menu(meta, &[ItemCategory::Consumable], ui);
             });
             egui::containers::ScrollArea::both()
                 .auto_shrink(false)
@@ -97,7 +163,7 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                 .show(ui, |ui| {
                     let mut card_names = meta.get_card_names();
 
-                    if meta.filters.card != "" {
+                    if !meta.filters.card.is_empty() {
                         card_names.retain(|name| {
                             let score = meta.matcher.fuzzy_match(
                                 &name[2..].to_lowercase(),
@@ -106,28 +172,11 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                             if score.is_some_and(|x| x > 1) {
                                 return true;
                             }
-                            return false;
+                            false
                         });
                     }
 
-                    for card_name in card_names.iter() {
-                        ui.horizontal(|ui| {
-                            ui.label(format!("{}", &card_name[2..].to_title_case()));
-                            let card = meta.get_item(&card_name);
-
-                            let card_val = card.unwrap();
-
-                            if card_val.can_be_alerted() {
-                                ui.checkbox(&mut card_val.alerted, "Alerted");
-                            }
-                            if card_val.can_be_discovered() {
-                                ui.checkbox(&mut card_val.discovered, "Discovered");
-                            }
-                            if card_val.can_be_unlocked() {
-                                ui.checkbox(&mut card_val.unlocked, "Unlocked");
-                            }
-                        });
-                    }
+                    draw_items(meta, &card_names, "Cards", ui);
                 });
             ui.separator();
         });
@@ -145,8 +194,9 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                         .hint_text("Filter Vouchers"),
                 );
                 if ui.button("Unlock All").clicked() {
-                    meta.unlock_all_type("v_");
+                    meta.record(|meta| meta.unlock_all_type("v_"));
                 }
+                draw_bulk_menu(meta, &[ItemCategory::Voucher], ui);
             });
             egui::containers::ScrollArea::both()
                 .auto_shrink(false)
@@ -156,7 +206,7 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                 .show(ui, |ui| {
                     let mut voucher_names = meta.get_voucher_names();
 
-                    if meta.filters.voucher != "" {
+                    if !meta.filters.voucher.is_empty() {
                         voucher_names.retain(|name| {
                             let score = meta.matcher.fuzzy_match(
                                 &name[2..].to_lowercase(),
@@ -168,24 +218,7 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                             false
                         });
                     }
-                    for voucher_name in voucher_names.iter() {
-                        ui.horizontal(|ui| {
-                            ui.label(format!("{}", &voucher_name[2..].to_title_case()));
-                            let voucher = meta.get_item(&voucher_name);
-
-                            let voucher_val = voucher.unwrap();
-
-                            if voucher_val.can_be_alerted() {
-                                ui.checkbox(&mut voucher_val.alerted, "Alerted");
-                            }
-                            if voucher_val.can_be_discovered() {
-                                ui.checkbox(&mut voucher_val.discovered, "Discovered");
-                            }
-                            if voucher_val.can_be_unlocked() {
-                                ui.checkbox(&mut voucher_val.unlocked, "Unlocked");
-                            }
-                        });
-                    }
+                    draw_items(meta, &voucher_names, "Vouchers", ui);
                 });
             ui.separator();
             ui.add(Label::new(
@@ -200,12 +233,15 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                         .hint_text("Filter Decks, Blinds, Tags, Edtions, and Booster Packs"),
                 );
                 if ui.button("Unlock All").clicked() {
-                    meta.unlock_all_type("b_");
-                    meta.unlock_all_type("e_");
-                    meta.unlock_all_type("tag_");
-                    meta.unlock_all_type("p_");
-                    meta.unlock_all_type("bl_");
+                    meta.record(|meta| {
+                        meta.unlock_all_type("b_");
+                        meta.unlock_all_type("e_");
+                        meta.unlock_all_type("tag_");
+                        meta.unlock_all_type("p_");
+                        meta.unlock_all_type("bl_");
+                    });
                 }
+                draw_bulk_menu(meta, &MISC_CATEGORIES, ui);
 
             });
 
@@ -217,7 +253,7 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                 .show(ui, |ui| {
                     let mut misc_names = meta.get_misc_names();
 
-                    if meta.filters.misc != "" {
+                    if !meta.filters.misc.is_empty() {
                         misc_names.retain(|name| {
                             let score = meta.matcher.fuzzy_match(
                                 &name[2..].to_lowercase(),
@@ -226,29 +262,164 @@ pub fn draw_meta(meta: &mut Meta, ctx: &Context, ui: &mut egui::Ui) {
                             if score.is_some_and(|x| x > 1) {
                                 return true;
                             }
-                            return false;
-                        });
-                    }
-                    for deck_name in misc_names.iter() {
-                        ui.horizontal(|ui| {
-                            ui.label(format!("{}", &deck_name[2..].to_title_case()));
-                            let misc = meta.get_item(&deck_name);
-
-                            let misc_val = misc.unwrap();
-                            if misc_val.can_be_alerted() {
-                                ui.checkbox(&mut misc_val.alerted, "Alerted");
-                            }
-                            if misc_val.can_be_discovered() {
-                                ui.checkbox(&mut misc_val.discovered, "Discovered");
-                            }
-                            if misc_val.can_be_unlocked() {
-                                ui.checkbox(&mut misc_val.unlocked, "Unlocked");
-                            }
+                            false
                         });
                     }
+                    draw_items(meta, &misc_names, "Misc", ui);
                 });
 
             ui.separator();
         });
     });
 }
+
+/// Draws the items of a section, with the items of each mod grouped under
+/// the name of the mod
+fn draw_items(
+    meta: &mut Meta,
+    names: &[String],
+    section: &str,
+    ui: &mut egui::Ui,
+) {
+    let mut mods: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    let mut unknown = Vec::new();
+    for name in names {
+        match meta.item_mod(name) {
+            Some(mod_prefix) => {
+                mods.entry(mod_prefix.to_string()).or_default().push(name);
+            }
+            None if !ItemKey::parse(name, meta.registry()).known => {
+                unknown.push(name)
+            }
+            None => draw_item(meta, name, ui),
+        }
+    }
+    for (mod_prefix, names) in mods {
+        egui::CollapsingHeader::new(mod_name(&mod_prefix))
+            .id_salt((section, mod_prefix))
+            .show(ui, |ui| {
+                for name in names {
+                    draw_item(meta, name, ui);
+                }
+            });
+    }
+    if !unknown.is_empty() {
+        egui::CollapsingHeader::new("Unknown")
+            .id_salt((section, "Unknown"))
+            .show(ui, |ui| {
+                ui.label("Items not in the registry or from a known mod");
+                for name in unknown {
+                    draw_item(meta, name, ui);
+                }
+            });
+    }
+}
+
+/// Draws the mods the items are from, with bulk edits for each one
+/// Nothing is drawn for a meta without modded items
+fn draw_mods(meta: &mut Meta, ui: &mut egui::Ui) {
+    let mods = meta.mods();
+    if mods.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new("Mods").show(ui, |ui| {
+        egui::Grid::new("Mods Grid").striped(true).show(ui, |ui| {
+            for mod_prefix in mods.iter() {
+                let items = meta.mod_items(mod_prefix);
+                let mut categories: Vec<ItemCategory> = items
+                    .iter()
+                    .filter_map(|name| {
+                        ItemKey::parse(name, meta.registry()).category()
+                    })
+                    .collect();
+                categories.sort_by_key(|category| {
+                    ItemCategory::ALL.iter().position(|c| c == category)
+                });
+                categories.dedup();
+
+                ui.label(format!(
+                    "{} ({} items)",
+                    mod_name(mod_prefix),
+                    items.len()
+                ));
+                for (label, edit) in MOD_EDITS {
+                    ui.menu_button(label, |ui| {
+                        if ui.button("Everything").clicked() {
+                            meta.record(|meta| edit(meta, mod_prefix, None));
+                            ui.close_menu();
+                        }
+                        for category in categories.iter() {
+                            if ui.button(category.to_string()).clicked() {
+                                meta.record(|meta| {
+                                    edit(meta, mod_prefix, Some(*category))
+                                });
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                }
+                if ui
+                    .button("🗑 Remove Keys")
+                    .on_hover_text("For a mod that is no longer installed")
+                    .clicked()
+                {
+                    meta.record(|meta| meta.remove_mod(mod_prefix));
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// Draws the name and flags of an item
+/// Each flag that is changed is its own step in the undo history
+fn draw_item(meta: &mut Meta, name: &str, ui: &mut egui::Ui) {
+    let Some(item) = meta.get_item(name) else {
+        return;
+    };
+    let mut item = item.clone();
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label(ItemKey::parse(name, meta.registry()).name.to_title_case());
+        if item.can_be_alerted() {
+            changed |= ui.checkbox(&mut item.alerted, "Alerted").changed();
+        }
+        if item.can_be_discovered() {
+            changed |=
+                ui.checkbox(&mut item.discovered, "Discovered").changed();
+        }
+        if item.can_be_unlocked() {
+            changed |= ui.checkbox(&mut item.unlocked, "Unlocked").changed();
+        }
+    });
+
+    if changed {
+        meta.record(|meta| {
+            if let Some(current) = meta.get_item(name) {
+                *current = item;
+            }
+        });
+    }
+}
+
+/// Draws a menu of bulk edits for the categories of a section
+/// Each edit is one undo step, even when it covers several categories
+fn draw_bulk_menu(
+    meta: &mut Meta,
+    categories: &[ItemCategory],
+    ui: &mut egui::Ui,
+) {
+    ui.menu_button("More", |ui| {
+        for (label, edit) in BULK_EDITS {
+            if ui.button(label).clicked() {
+                meta.record(|meta| {
+                    for category in categories {
+                        edit(meta, *category);
+                    }
+                });
+                ui.close_menu();
+            }
+        }
+    });
+}
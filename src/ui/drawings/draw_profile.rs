@@ -1,10 +1,306 @@
 use crate::saves::Profile;
 
-pub fn draw_profile(
-    profile: &mut Profile,
-    ctx: &egui::Context,
-    ui: &mut egui::Ui,
-) {
-    // Draw the profile
-    todo!("Finish drawing the profile");
+use egui::{Context, Label};
+
+/// This is used to give the to_title_case method on str
+use inflector::Inflector;
+
+/// This is used to allow us to use the fuzzy_match method on FuzzyMatcher
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// The names of the vanilla stakes, in the order the game numbers them
+const STAKE_NAMES: [&str; 8] =
+    ["White", "Red", "Green", "Black", "Blue", "Purple", "Orange", "Gold"];
+
+pub fn draw_profile(profile: &mut Profile, ctx: &Context, ui: &mut egui::Ui) {
+    let window_size = ctx.screen_rect().size();
+    let search_width = window_size.x * 0.3;
+
+    egui::containers::ScrollArea::vertical()
+        .auto_shrink(false)
+        .id_salt("Profile")
+        .show(ui, |ui| {
+            ui.vertical(|ui| {
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Career Stats")
+                        .color(egui::Color32::GOLD),
+                )
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("Career Stats Grid").striped(true).show(
+                        ui,
+                        |ui| {
+                            let stats = &mut profile.career_stats;
+                            for (key, value) in stats.counts_mut() {
+                                ui.label(key[2..].to_title_case());
+                                ui.add(egui::DragValue::new(value));
+                                ui.end_row();
+                            }
+                            for (key, value) in stats.amounts_mut() {
+                                ui.label(key[2..].to_title_case());
+                                ui.add(
+                                    egui::DragValue::new(value)
+                                        .range(0.0..=f64::MAX)
+                                        .prefix("$"),
+                                );
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Joker Usage")
+                        .color(egui::Color32::GREEN),
+                )
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut profile.filters.joker)
+                            .desired_width(search_width)
+                            .hint_text("Filter Jokers"),
+                    );
+                    let names = filter_names(
+                        &profile.matcher,
+                        profile.joker_usage.keys(),
+                        &profile.filters.joker,
+                    );
+                    for name in names.iter() {
+                        let usage = profile.joker_usage.get_mut(name).unwrap(); // Safe to unwrap because the names come from the map
+                        egui::CollapsingHeader::new(item_label(name))
+                            .id_salt(name)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("Times Used");
+                                    count_value(ui, &mut usage.count);
+                                });
+                                egui::Grid::new(format!("{} Stakes", name))
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label("Stake");
+                                        ui.label("Wins");
+                                        ui.label("Losses");
+                                        ui.end_row();
+                                        for (i, stake) in
+                                            STAKE_NAMES.iter().enumerate()
+                                        {
+                                            ui.label(*stake);
+                                            count_value(ui, &mut usage.wins[i]);
+                                            count_value(
+                                                ui,
+                                                &mut usage.losses[i],
+                                            );
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Consumable Usage")
+                        .color(egui::Color32::LIGHT_BLUE),
+                )
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(
+                            &mut profile.filters.consumable,
+                        )
+                        .desired_width(search_width)
+                        .hint_text("Filter Consumables"),
+                    );
+                    let names = filter_names(
+                        &profile.matcher,
+                        profile.consumable_usage.keys(),
+                        &profile.filters.consumable,
+                    );
+                    egui::Grid::new("Consumable Grid").striped(true).show(
+                        ui,
+                        |ui| {
+                            for name in names.iter() {
+                                let usage = profile
+                                    .consumable_usage
+                                    .get_mut(name)
+                                    .unwrap(); // Safe to unwrap because the names come from the map
+                                ui.label(item_label(name));
+                                count_value(ui, &mut usage.count);
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Deck Stakes")
+                        .color(egui::Color32::LIGHT_RED),
+                )
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut profile.filters.deck)
+                            .desired_width(search_width)
+                            .hint_text("Filter Decks"),
+                    );
+                    let names = filter_names(
+                        &profile.matcher,
+                        profile.deck_stakes.keys(),
+                        &profile.filters.deck,
+                    );
+                    egui::Grid::new("Deck Stakes Grid").striped(true).show(
+                        ui,
+                        |ui| {
+                            for name in names.iter() {
+                                let stake =
+                                    profile.deck_stakes.get_mut(name).unwrap(); // Safe to unwrap because the names come from the map
+                                // Modded stakes can go past the vanilla ones
+                                let max =
+                                    STAKE_NAMES.len().max(*stake as usize);
+                                ui.label(item_label(name));
+                                ui.add(
+                                    egui::DragValue::new(stake)
+                                        .range(0..=max)
+                                        .custom_formatter(|n, _| {
+                                            stake_label(n as usize)
+                                        }),
+                                );
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Challenges")
+                        .color(egui::Color32::PURPLE),
+                )
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(
+                            &mut profile.filters.challenge,
+                        )
+                        .desired_width(search_width)
+                        .hint_text("Filter Challenges"),
+                    );
+                    let progress = &mut profile.challenge_progress;
+                    let ids = progress
+                        .unlocked
+                        .union(&progress.completed)
+                        .cloned()
+                        .collect::<Vec<String>>();
+                    let ids = filter_names(
+                        &profile.matcher,
+                        ids.iter(),
+                        &profile.filters.challenge,
+                    );
+                    for id in ids.iter() {
+                        ui.horizontal(|ui| {
+                            ui.label(item_label(id));
+                            let mut unlocked = progress.unlocked.contains(id);
+                            if ui.checkbox(&mut unlocked, "Unlocked").changed()
+                            {
+                                if unlocked {
+                                    progress.unlocked.insert(id.clone());
+                                } else {
+                                    progress.unlocked.remove(id);
+                                }
+                            }
+                            let mut completed = progress.completed.contains(id);
+                            if ui
+                                .checkbox(&mut completed, "Completed")
+                                .changed()
+                            {
+                                if completed {
+                                    progress.completed.insert(id.clone());
+                                } else {
+                                    progress.completed.remove(id);
+                                }
+                            }
+                        });
+                    }
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("High Scores")
+                        .color(egui::Color32::ORANGE),
+                )
+                .show(ui, |ui| {
+                    let mut keys: Vec<String> =
+                        profile.high_scores.keys().cloned().collect();
+                    keys.sort();
+                    egui::Grid::new("High Scores Grid").striped(true).show(
+                        ui,
+                        |ui| {
+                            for key in keys.iter() {
+                                let score =
+                                    profile.high_scores.get_mut(key).unwrap(); // Safe to unwrap because the keys come from the map
+                                if score.label.is_empty() {
+                                    ui.label(key.to_title_case());
+                                } else {
+                                    ui.label(&score.label);
+                                }
+                                ui.add(
+                                    egui::DragValue::new(&mut score.amt)
+                                        .range(0.0..=f64::MAX),
+                                );
+                                if let Some(tot) = score.tot {
+                                    ui.add(Label::new(format!("of {}", tot)));
+                                }
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+            });
+        });
+}
+
+/// Sorts the names and keeps the ones that match the filter
+fn filter_names<'a>(
+    matcher: &SkimMatcherV2,
+    names: impl Iterator<Item = &'a String>,
+    filter: &str,
+) -> Vec<String> {
+    let mut names: Vec<String> = names.cloned().collect();
+    names.sort();
+
+    if !filter.is_empty() {
+        names.retain(|name| {
+            let score = matcher
+                .fuzzy_match(&name.to_lowercase(), &filter.to_lowercase());
+            if score.is_some_and(|x| x > 1) {
+                return true;
+            }
+            false
+        });
+    }
+    names
+}
+
+/// Turns an item key like j_blueprint into a name like Blueprint
+fn item_label(name: &str) -> String {
+    match name.split_once('_') {
+        Some((_, rest)) if !rest.is_empty() => rest.to_title_case(),
+        _ => name.to_title_case(),
+    }
+}
+
+/// Turns a stake number into its name
+fn stake_label(stake: usize) -> String {
+    match stake {
+        0 => "None".to_string(),
+        n => STAKE_NAMES
+            .get(n - 1)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| n.to_string()),
+    }
+}
+
+/// Draws an editable count
+/// Counts are stored as u128, which egui can not drag, so a u64 stands in
+fn count_value(ui: &mut egui::Ui, value: &mut u128) -> egui::Response {
+    let mut shown = (*value).min(u64::MAX as u128) as u64;
+    let response = ui.add(egui::DragValue::new(&mut shown));
+    if response.changed() {
+        *value = shown as u128;
+    }
+    response
 }
@@ -0,0 +1,78 @@
+use crate::saves::{SAVEGAME_COUNTS, SaveGame};
+
+/// This is used to give the to_title_case method on str
+use inflector::Inflector;
+
+pub fn draw_savegame(save: &mut SaveGame, ui: &mut egui::Ui) {
+    egui::containers::ScrollArea::vertical()
+        .auto_shrink(false)
+        .id_salt("Save")
+        .show(ui, |ui| {
+            ui.vertical(|ui| {
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Run").color(egui::Color32::GOLD),
+                )
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("Run Grid").striped(true).show(ui, |ui| {
+                        ui.label("Deck");
+                        ui.label(&save.deck);
+                        ui.end_row();
+                        ui.label("Stake");
+                        ui.label(save.stake.to_string());
+                        ui.end_row();
+                        ui.label("Seed");
+                        ui.label(&save.seed);
+                        ui.end_row();
+                        ui.label("Version");
+                        ui.label(&save.version);
+                        ui.end_row();
+                    });
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Values").color(egui::Color32::GREEN),
+                )
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("Values Grid").striped(true).show(
+                        ui,
+                        |ui| {
+                            ui.label("Dollars");
+                            ui.add(
+                                egui::DragValue::new(&mut save.dollars)
+                                    .prefix("$"),
+                            );
+                            ui.end_row();
+                            for (value, (label, _)) in
+                                save.counts.iter_mut().zip(SAVEGAME_COUNTS)
+                            {
+                                ui.label(label);
+                                ui.add(
+                                    egui::DragValue::new(value)
+                                        .range(0..=i64::MAX),
+                                );
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+
+                egui::CollapsingHeader::new(
+                    egui::RichText::new("Jokers")
+                        .color(egui::Color32::LIGHT_BLUE),
+                )
+                .show(ui, |ui| {
+                    if save.jokers.is_empty() {
+                        ui.label("No Jokers");
+                    }
+                    for joker in save.jokers.iter() {
+                        match joker.split_once('_') {
+                            Some((_, name)) => ui.label(name.to_title_case()),
+                            None => ui.label(joker),
+                        };
+                    }
+                });
+            });
+        });
+}
@@ -0,0 +1,80 @@
+use crate::saves::{GAME_SPEEDS, LANGUAGES, Settings};
+
+pub fn draw_settings(settings: &mut Settings, ui: &mut egui::Ui) {
+    egui::containers::ScrollArea::vertical()
+        .auto_shrink(false)
+        .id_salt("Game Settings")
+        .show(ui, |ui| {
+            egui::Grid::new("Game Settings Grid").striped(true).show(
+                ui,
+                |ui| {
+                    ui.label("Language");
+                    let selected = LANGUAGES
+                        .iter()
+                        .find(|(key, _)| *key == settings.language)
+                        .map(|(_, label)| label.to_string())
+                        .unwrap_or_else(|| settings.language.clone());
+                    egui::ComboBox::from_id_salt("Language")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (key, label) in LANGUAGES {
+                                ui.selectable_value(
+                                    &mut settings.language,
+                                    key.to_string(),
+                                    label,
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Game Speed");
+                    ui.horizontal(|ui| {
+                        for speed in GAME_SPEEDS {
+                            ui.selectable_value(
+                                &mut settings.game_speed,
+                                speed,
+                                format!("{}x", speed),
+                            );
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Main Volume");
+                    ui.add(egui::Slider::new(
+                        &mut settings.volume,
+                        0.0..=100.0,
+                    ));
+                    ui.end_row();
+
+                    ui.label("Music Volume");
+                    ui.add(egui::Slider::new(
+                        &mut settings.music_volume,
+                        0.0..=100.0,
+                    ));
+                    ui.end_row();
+
+                    ui.label("Game Sounds Volume");
+                    ui.add(egui::Slider::new(
+                        &mut settings.game_sounds_volume,
+                        0.0..=100.0,
+                    ));
+                    ui.end_row();
+
+                    ui.label("Screen Shake");
+                    ui.add(egui::Slider::new(
+                        &mut settings.screenshake,
+                        0.0..=100.0,
+                    ));
+                    ui.end_row();
+
+                    ui.label("High Contrast Cards");
+                    ui.checkbox(&mut settings.high_contrast, "");
+                    ui.end_row();
+
+                    ui.label("Crash Reports");
+                    ui.checkbox(&mut settings.crash_reports, "");
+                    ui.end_row();
+                },
+            );
+        });
+}
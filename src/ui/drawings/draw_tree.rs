@@ -0,0 +1,63 @@
+use crate::lua::{SaveKey, SaveTable, SaveValue, format_path};
+
+/// Draws any save file as a tree of its tables
+/// This is used for files that are not one of the known save types
+pub fn draw_tree(value: &mut SaveValue, ui: &mut egui::Ui) {
+    egui::containers::ScrollArea::vertical()
+        .auto_shrink(false)
+        .id_salt("Tree")
+        .show(ui, |ui| match value {
+            SaveValue::Table(table) => draw_table(table, &mut Vec::new(), ui),
+            value => draw_value(value, ui),
+        });
+}
+
+/// Draws the entries of a table, with a collapsible header for each subtable
+fn draw_table(
+    table: &mut SaveTable,
+    path: &mut Vec<SaveKey>,
+    ui: &mut egui::Ui,
+) {
+    for (key, value) in table.iter_mut() {
+        path.push(key.clone());
+        match value {
+            SaveValue::Table(subtable) => {
+                egui::CollapsingHeader::new(format!(
+                    "{} ({})",
+                    key,
+                    subtable.len()
+                ))
+                .id_salt(format_path(path))
+                .show(ui, |ui| draw_table(subtable, path, ui));
+            }
+            value => {
+                ui.horizontal(|ui| {
+                    ui.label(key.to_string());
+                    draw_value(value, ui);
+                });
+            }
+        }
+        path.pop();
+    }
+}
+
+/// Draws an editable value that is not a table
+fn draw_value(value: &mut SaveValue, ui: &mut egui::Ui) {
+    match value {
+        SaveValue::String(s) => {
+            ui.text_edit_singleline(s);
+        }
+        SaveValue::Integer(i) => {
+            ui.add(egui::DragValue::new(i));
+        }
+        SaveValue::Float(f) => {
+            ui.add(egui::DragValue::new(f));
+        }
+        SaveValue::Bool(b) => {
+            ui.checkbox(b, "");
+        }
+        SaveValue::Table(table) => {
+            ui.label(format!("{} entries", table.len()));
+        }
+    }
+}
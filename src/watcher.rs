@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often a watched file is checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watches a file for changes by polling its modified time and size
+///
+/// The game rewrites its files in place, so polling the metadata is enough
+/// and works the same on every platform.
+#[derive(Debug, Clone)]
+pub struct FileWatcher {
+    /// The file being watched
+    path: PathBuf,
+    /// The modified time and size the file had when it was last seen
+    seen: Option<(SystemTime, u64)>,
+    /// When the file was last checked
+    last_poll: Instant,
+}
+
+impl FileWatcher {
+    /// Starts watching a file, as it is now
+    pub fn new(path: &Path) -> Self {
+        FileWatcher {
+            path: path.to_path_buf(),
+            seen: stamp(path),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Returns the file being watched
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks if the file changed since it was last seen
+    /// A change is only reported once, the file is then seen as it is now
+    pub fn changed(&mut self) -> bool {
+        self.last_poll = Instant::now();
+        let stamp = stamp(&self.path);
+        // A file that was deleted is not a change that can be reloaded
+        if stamp.is_none() || stamp == self.seen {
+            return false;
+        }
+        self.seen = stamp;
+        true
+    }
+
+    /// Checks if the file changed, at most once every POLL_INTERVAL
+    pub fn poll(&mut self) -> bool {
+        self.last_poll.elapsed() >= POLL_INTERVAL && self.changed()
+    }
+
+    /// Marks the file as seen as it is now
+    /// Used after the app writes the file, so its own writes are not changes
+    pub fn mark_seen(&mut self) {
+        self.seen = stamp(&self.path);
+    }
+}
+
+/// Returns the modified time and size of a file, if it exists
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
@@ -0,0 +1,62 @@
+use bal_save::backup;
+use std::path::PathBuf;
+
+/// Makes an empty directory for a test, with a save file to overwrite
+fn setup(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("meta.jkr");
+    std::fs::write(&file, b"0").unwrap();
+    (file, dir.join("backups"))
+}
+
+#[test]
+fn overwrites_are_backed_up_and_pruned() {
+    let (file, backups) = setup("bal-save-backup-test");
+
+    // Nothing to back up the first time a new file is written
+    let new = file.with_file_name("new.jkr");
+    assert!(
+        backup::write_with_backup(&new, b"x", &backups, 3).unwrap().is_none()
+    );
+
+    for n in 1..=5 {
+        let backup = backup::write_with_backup(
+            &file,
+            n.to_string().as_bytes(),
+            &backups,
+            3,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(backup.read().unwrap(), (n - 1).to_string().as_bytes());
+        assert_eq!(backup.original, file.canonicalize().unwrap());
+    }
+    assert_eq!(std::fs::read(&file).unwrap(), b"5");
+
+    // Only the newest three are kept, and they are listed newest first
+    let list = backup::list_backups(&backups);
+    let data: Vec<Vec<u8>> = list.iter().map(|b| b.read().unwrap()).collect();
+    assert_eq!(data, vec![b"4".to_vec(), b"3".to_vec(), b"2".to_vec()]);
+
+    // Keeping none turns backups off
+    assert!(
+        backup::write_with_backup(&file, b"6", &backups, 0).unwrap().is_none()
+    );
+    assert_eq!(backup::list_backups(&backups).len(), 3);
+}
+
+#[test]
+fn restore_backs_up_the_current_file() {
+    let (file, backups) = setup("bal-save-restore-test");
+    let backup =
+        backup::write_with_backup(&file, b"1", &backups, 10).unwrap().unwrap();
+
+    backup::restore(&backup, &backups, 10).unwrap();
+    assert_eq!(std::fs::read(&file).unwrap(), b"0");
+
+    let list = backup::list_backups(&backups);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].read().unwrap(), b"1");
+}
@@ -0,0 +1,49 @@
+use bal_save::enums::{ItemCategory, MetaFlag};
+use bal_save::registry::use_bundled;
+use bal_save::saves::Meta;
+
+#[test]
+fn bulk_edits_work_per_category_and_flag() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_defaults();
+
+    meta.discover_category(ItemCategory::Joker);
+    let jokers = meta.category_stats(ItemCategory::Joker);
+    assert_eq!(jokers.discovered.done, jokers.discovered.total);
+    assert!(!meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(!meta.get_item("v_grabber").unwrap().discovered);
+
+    // Unlocking uses each item's own flags, blinds are never unlocked
+    meta.unlock_category(ItemCategory::Blind);
+    meta.set_flag_category(ItemCategory::Blind, MetaFlag::Unlocked, true);
+    assert!(meta.get_item("bl_ox").unwrap().discovered);
+    assert!(!meta.get_item("bl_ox").unwrap().unlocked);
+
+    meta.clear_alerts(ItemCategory::Blind);
+    assert_eq!(meta.category_stats(ItemCategory::Blind).alerted.done, 0);
+    assert!(meta.get_item("bl_ox").unwrap().discovered);
+
+    // Resetting puts back what a new profile has
+    meta.unlock_category(ItemCategory::Deck);
+    meta.reset_category(ItemCategory::Deck);
+    meta.reset_category(ItemCategory::Joker);
+    meta.reset_category(ItemCategory::Blind);
+    assert!(meta.diff(&Meta::from_defaults()).is_empty());
+
+    meta.lock_category(ItemCategory::Deck);
+    assert!(!meta.get_item("b_red").unwrap().unlocked);
+    assert_eq!(meta.category_stats(ItemCategory::Deck).unlocked.done, 0);
+}
+
+#[test]
+fn unlock_all_type_uses_each_items_own_entry() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_defaults();
+    meta.unlock_all_type("");
+
+    // Blinds can not be unlocked, jokers can
+    assert!(meta.get_item("bl_ox").unwrap().discovered);
+    assert!(!meta.get_item("bl_ox").unwrap().unlocked);
+    assert!(meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(meta.get_item("j_joker").unwrap().unlocked);
+}
@@ -0,0 +1,180 @@
+use bal_save::lua;
+use bal_save::saves::Meta;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Runs the CLI with only the bundled items, so the user's items files are
+/// never read
+fn bal_save(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_bal-save"));
+    command.arg("--items-dir").arg(dir.join("items"));
+    command
+}
+
+/// Makes an empty directory for a test, so tests never share files
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("bal-save-cli-tests").join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The directory a test keeps backups in, so the real one is never touched
+fn backup_dir(dir: &Path) -> PathBuf {
+    dir.join("backups")
+}
+
+/// Writes a fixture to a file of its own, so tests can edit it
+fn fixture_file(dir: &Path, name: &str, text: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, lua::deflate(text).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn set_and_unlock_edit_meta_files() {
+    let dir = test_dir("set");
+    let input =
+        fixture_file(&dir, "set.jkr", include_str!("fixtures/meta.txt"));
+    let output = input.with_file_name("set_out.jkr");
+
+    let status = bal_save(&dir)
+        .arg("set")
+        .arg(&input)
+        .args(["j_blueprint", "--discovered", "true", "-o"])
+        .arg(&output)
+        .arg("--backup-dir")
+        .arg(backup_dir(&dir))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut meta =
+        Meta::from_lua_table(std::fs::read(&output).unwrap()).unwrap();
+    assert!(meta.get_item("j_blueprint").unwrap().discovered);
+
+    let status = bal_save(&dir)
+        .arg("unlock")
+        .arg(&output)
+        .args(["--type", "v_", "--backup-dir"])
+        .arg(backup_dir(&dir))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut meta =
+        Meta::from_lua_table(std::fs::read(&output).unwrap()).unwrap();
+    assert!(meta.get_item("v_grabber").unwrap().unlocked);
+    assert!(!meta.get_item("j_blueprint").unwrap().unlocked);
+}
+
+#[test]
+fn errors_exit_non_zero() {
+    let dir = test_dir("errors");
+    let profile =
+        fixture_file(&dir, "profile.jkr", include_str!("fixtures/profile.txt"));
+    let out = bal_save(&dir).arg("unlock").arg(&profile).output().unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Meta"));
+
+    let meta =
+        fixture_file(&dir, "unknown.jkr", include_str!("fixtures/meta.txt"));
+    let out = bal_save(&dir)
+        .arg("set")
+        .arg(&meta)
+        .args(["j_missing", "--unlocked", "true"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+
+    let info = bal_save(&dir).arg("info").arg(&profile).output().unwrap();
+    assert!(info.status.success());
+    assert!(String::from_utf8_lossy(&info.stdout).starts_with("Kind: Profile"));
+}
+
+#[test]
+fn diff_prints_changed_items() {
+    let dir = test_dir("diff");
+    let old =
+        fixture_file(&dir, "diff_old.jkr", include_str!("fixtures/meta.txt"));
+    let new = fixture_file(
+        &dir,
+        "diff_new.jkr",
+        &include_str!("fixtures/meta.txt")
+            .replace("[\"v_grabber\"]=false", "[\"v_grabber\"]=true"),
+    );
+    let out = bal_save(&dir).arg("diff").arg(&old).arg(&new).output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "~ v_grabber: unlocked false → true\n"
+    );
+}
+
+#[test]
+fn import_meta_checks_the_target_and_writes_to_the_output() {
+    let dir = test_dir("import");
+    let export = dir.join("import.csv");
+    std::fs::write(
+        &export,
+        "name,alerted,discovered,unlocked\nj_blueprint,true,true,true\n",
+    )
+    .unwrap();
+
+    let profile = fixture_file(
+        &dir,
+        "import_profile.jkr",
+        include_str!("fixtures/profile.txt"),
+    );
+    let out = bal_save(&dir)
+        .arg("import-meta")
+        .arg(&export)
+        .arg(&profile)
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Meta"));
+
+    let meta =
+        fixture_file(&dir, "import.jkr", include_str!("fixtures/meta.txt"));
+    let output = dir.join("import_out.jkr");
+    let status = bal_save(&dir)
+        .arg("import-meta")
+        .arg(&export)
+        .arg(&meta)
+        .arg("-o")
+        .arg(&output)
+        .arg("--backup-dir")
+        .arg(backup_dir(&dir))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut imported =
+        Meta::from_lua_table(std::fs::read(&output).unwrap()).unwrap();
+    assert!(imported.get_item("j_blueprint").unwrap().unlocked);
+    // The target is left as it was
+    let mut target =
+        Meta::from_lua_table(std::fs::read(&meta).unwrap()).unwrap();
+    assert!(!target.get_item("j_blueprint").unwrap().unlocked);
+}
+
+#[test]
+fn set_fails_for_flags_the_item_can_not_have() {
+    let dir = test_dir("set_flag");
+    let text = include_str!("fixtures/meta.txt");
+    let meta = fixture_file(&dir, "meta.jkr", text);
+    let out = bal_save(&dir)
+        .arg("set")
+        .arg(&meta)
+        .args(["bl_ox", "--discovered", "true", "--unlocked", "true"])
+        .arg("--backup-dir")
+        .arg(backup_dir(&dir))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr)
+            .contains("'bl_ox' can not be unlocked")
+    );
+    // Nothing is written, not even the flags it can have
+    let data = std::fs::read(&meta).unwrap();
+    assert_eq!(lua::inflate(&data).unwrap(), text);
+}
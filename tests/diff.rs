@@ -0,0 +1,60 @@
+use bal_save::diff::{Change, Diff, diff_values};
+use bal_save::lua::{self, SaveValue};
+
+fn value(text: &str) -> SaveValue {
+    lua::parse(text).unwrap()
+}
+
+#[test]
+fn tree_diff_reports_paths() {
+    let old = value(include_str!("fixtures/save.txt"));
+    let mut new = old.clone();
+    new.set_path("GAME.dollars", SaveValue::Integer(10)).unwrap();
+    new.set_path("GAME.chips", SaveValue::Float(0.5)).unwrap();
+    new.set_path("GAME.round", SaveValue::Integer(3)).unwrap();
+    new.remove_path("tags");
+
+    let differences = diff_values(&old, &new);
+    let lines: Vec<String> =
+        differences.iter().map(|d| d.to_string()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "~ GAME.dollars: -3 → 10",
+            "+ GAME.round = 3",
+            "- tags = table (0 keys)",
+        ]
+    );
+    assert_eq!(
+        differences[0].change,
+        Change::Changed(SaveValue::Integer(-3), SaveValue::Integer(10))
+    );
+    assert!(diff_values(&old, &old).is_empty());
+}
+
+#[test]
+fn meta_diff_is_per_item() {
+    let old = value(include_str!("fixtures/meta.txt"));
+    let new = value(
+        &include_str!("fixtures/meta.txt")
+            .replace("[\"v_grabber\"]=false", "[\"v_grabber\"]=true")
+            .replace(
+                "[\"c_fool\"]=false,",
+                "[\"c_fool\"]=false,[\"j_blueprint\"]=true,",
+            ),
+    );
+
+    let diff = Diff::new(&old, &new, false).unwrap();
+    assert_eq!(
+        diff.lines(),
+        vec![
+            "~ j_blueprint: discovered false → true",
+            "~ v_grabber: unlocked false → true",
+        ]
+    );
+
+    // The raw diff compares the tables key by key instead
+    let raw = Diff::new(&old, &new, true).unwrap();
+    assert_eq!(raw.lines().len(), 2);
+    assert!(raw.lines()[0].starts_with("~ unlocked.v_grabber"));
+}
@@ -0,0 +1,53 @@
+use bal_save::enums::{ExportFormat, SaveError};
+use bal_save::lua;
+use bal_save::registry::use_bundled;
+use bal_save::saves::Meta;
+
+#[test]
+fn exports_round_trip_in_every_format() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_lua_table(
+        lua::deflate(include_str!("fixtures/meta.txt")).unwrap(),
+    )
+    .unwrap();
+    meta.get_item("j_blueprint").unwrap().unlocked = true;
+
+    for format in ExportFormat::ALL {
+        let text = meta.export(format).unwrap();
+        let mut imported = Meta::import(&text, format, false).unwrap();
+        assert!(meta.diff(&imported).is_empty(), "{}", format);
+        assert!(imported.get_item("j_blueprint").unwrap().unlocked);
+    }
+
+    let csv = meta.export(ExportFormat::Csv).unwrap();
+    assert!(csv.starts_with("name,alerted,discovered,unlocked\n"));
+    assert!(csv.contains("\nj_blueprint,false,false,true\n"));
+}
+
+#[test]
+fn imports_are_validated() {
+    assert!(use_bundled());
+    let csv = "name,alerted,discovered,unlocked\n\
+        j_joker,true,true,true\n\
+        j_joker,true,true,true\n\
+        bl_ox,false,true,true\n\
+        j_mod_thing,true,true,true\n";
+    let err = Meta::import(csv, ExportFormat::Csv, false).err().unwrap();
+    let SaveError::Import(reason) = err else {
+        panic!("expected an import error");
+    };
+    assert_eq!(
+        reason,
+        "j_joker is listed more than once, bl_ox can not be unlocked, \
+        j_mod_thing is not a known item"
+    );
+
+    // Modded items can be allowed, the other problems still fail
+    let modded =
+        "name,alerted,discovered,unlocked\nj_mod_thing,true,true,true\n";
+    let mut meta = Meta::import(modded, ExportFormat::Csv, true).unwrap();
+    assert!(meta.get_item("j_mod_thing").unwrap().unlocked);
+    assert!(Meta::import(csv, ExportFormat::Csv, true).is_err());
+
+    assert!(Meta::import("{", ExportFormat::Json, false).is_err());
+}
@@ -0,0 +1,61 @@
+use bal_save::locator;
+use std::path::{Path, PathBuf};
+
+/// Makes the Balatro save directory of a Steam library, with some files
+fn make_saves(library: &Path, files: &[&str]) -> PathBuf {
+    let root = library.join(
+        "steamapps/compatdata/2379780/pfx/drive_c/users/steamuser/AppData/Roaming/Balatro",
+    );
+    for file in files {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"").unwrap();
+    }
+    root
+}
+
+#[test]
+fn finds_saves_in_native_flatpak_and_custom_libraries() {
+    let home = std::env::temp_dir().join("bal-save-locator-test");
+    let _ = std::fs::remove_dir_all(&home);
+
+    let native = home.join(".local/share/Steam");
+    let flatpak = home.join(".var/app/com.valvesoftware.Steam/data/Steam");
+    let custom = home.join("Games/SteamLibrary");
+    let native_saves =
+        make_saves(&native, &["settings.jkr", "1/meta.jkr", "1/profile.jkr"]);
+    let flatpak_saves = make_saves(&flatpak, &["2/save.jkr"]);
+    let custom_saves = make_saves(&custom, &["3/meta.jkr"]);
+
+    let vdf = format!(
+        "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t\t\"apps\"\n\t\t{{\n\t\t\t\"2379780\"\t\t\"1\"\n\t\t}}\n\t}}\n}}\n",
+        native.display(),
+        custom.display()
+    );
+    std::fs::write(native.join("steamapps/libraryfolders.vdf"), vdf).unwrap();
+
+    let locations = locator::find_save_locations_in(&home);
+    let roots: Vec<&PathBuf> = locations.iter().map(|l| &l.root).collect();
+    assert_eq!(roots, vec![&native_saves, &custom_saves, &flatpak_saves]);
+
+    let native = &locations[0];
+    assert!(native.settings.is_some());
+    assert_eq!(native.slots.len(), 1);
+    assert_eq!(native.slots[0].number, 1);
+    assert!(native.slots[0].meta.is_some());
+    assert!(native.slots[0].save.is_none());
+    assert_eq!(locations[2].slots[0].number, 2);
+    assert!(locations[2].slots[0].save.is_some());
+}
+
+#[test]
+fn library_paths_are_read_from_vdf() {
+    let vdf = "\"libraryfolders\" { \"0\" { \"path\" \"C:\\\\Games\\\\Steam\" \"label\" \"\" } }";
+    assert_eq!(
+        locator::library_folders(vdf),
+        vec![PathBuf::from("C:\\Games\\Steam")]
+    );
+    // "path" is only a key in key position, not as a value or a section
+    let vdf = "\"libraryfolders\" { \"0\" { \"label\" \"path\" \"path\" \"/games\" \"apps\" { \"path\" { \"x\" \"y\" } } } }";
+    assert_eq!(locator::library_folders(vdf), vec![PathBuf::from("/games")]);
+}
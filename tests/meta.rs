@@ -0,0 +1,108 @@
+use bal_save::enums::MergeStrategy;
+use bal_save::lua;
+use bal_save::registry::use_bundled;
+use bal_save::saves::Meta;
+
+const META: &str = include_str!("fixtures/meta.txt");
+
+#[test]
+fn merging_unlocks_keeps_flags_set_in_either() {
+    assert!(use_bundled());
+    let mut mine = Meta::from_lua_table(lua::deflate(META).unwrap()).unwrap();
+    mine.get_item("v_grabber").unwrap().unlocked = true;
+
+    let mut theirs = Meta::from_defaults();
+    theirs.get_item("j_blueprint").unwrap().discovered = true;
+    theirs.get_item("j_joker").unwrap().unlocked = false;
+
+    mine.merge(&theirs, MergeStrategy::Union);
+    assert!(mine.get_item("v_grabber").unwrap().unlocked);
+    assert!(mine.get_item("j_blueprint").unwrap().discovered);
+    assert!(mine.get_item("j_joker").unwrap().unlocked);
+    assert!(!mine.get_item("j_blueprint").unwrap().unlocked);
+}
+
+#[test]
+fn bulk_edits_are_undone_in_one_step() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_defaults();
+    assert!(!meta.can_undo());
+
+    meta.record(|meta| meta.get_item("j_blueprint").unwrap().alerted = true);
+    meta.record(|meta| {
+        meta.unlock_all_type("j_");
+        meta.unlock_all_type("v_");
+    });
+    assert!(meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(meta.get_item("v_grabber").unwrap().discovered);
+
+    assert!(meta.undo());
+    assert!(!meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(!meta.get_item("v_grabber").unwrap().discovered);
+    assert!(meta.get_item("j_blueprint").unwrap().alerted);
+
+    assert!(meta.redo());
+    assert!(meta.get_item("v_grabber").unwrap().discovered);
+    assert!(!meta.can_redo());
+
+    assert!(meta.undo() && meta.undo());
+    assert!(!meta.get_item("j_blueprint").unwrap().alerted);
+    assert!(!meta.undo());
+
+    // A new edit drops the edits that were undone
+    meta.record(|meta| meta.get_item("j_blueprint").unwrap().discovered = true);
+    assert!(!meta.can_redo());
+}
+
+#[test]
+fn merge_strategies_combine_flags() {
+    assert!(use_bundled());
+    // Left has j_joker fully and v_grabber unlocked, right has j_joker only
+    // discovered and a modded item the left does not have
+    let left = || Meta::from_lua_table(lua::deflate(META).unwrap()).unwrap();
+    let right = Meta::from_lua_table(
+        lua::deflate(
+            "return {[\"alerted\"]={},[\"discovered\"]={[\"j_joker\"]=true,[\"j_mod_thing\"]=true,},[\"unlocked\"]={[\"j_joker\"]=false,[\"v_grabber\"]=true,},}",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let mut union = left();
+    let changes = union.merge(&right, MergeStrategy::Union);
+    // b_red is discovered in a new profile, which the right file is
+    let lines: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "b_red: alerted false → true, discovered false → true",
+            "j_mod_thing: added",
+            "v_grabber: unlocked false → true",
+        ]
+    );
+    assert!(union.get_item("v_grabber").unwrap().unlocked);
+    assert!(union.get_item("j_joker").unwrap().unlocked);
+    assert!(union.get_item("j_mod_thing").unwrap().discovered);
+
+    let mut intersection = left();
+    intersection.merge(&right, MergeStrategy::Intersection);
+    assert!(!intersection.get_item("j_joker").unwrap().unlocked);
+    assert!(intersection.get_item("j_joker").unwrap().discovered);
+    assert!(!intersection.get_item("j_mod_thing").unwrap().discovered);
+
+    let mut prefer_left = left();
+    prefer_left.merge(&right, MergeStrategy::PreferLeft);
+    assert!(prefer_left.get_item("j_joker").unwrap().unlocked);
+    assert!(prefer_left.get_item("j_mod_thing").unwrap().discovered);
+
+    let mut prefer_right = left();
+    let changes = prefer_right.merge(&right, MergeStrategy::PreferRight);
+    assert!(!prefer_right.get_item("j_joker").unwrap().unlocked);
+    assert!(changes.iter().any(|c| c.to_string()
+        == "j_joker: alerted true → false, unlocked true → false"));
+
+    // The whole merge is one undo step
+    assert!(prefer_right.undo());
+    assert!(prefer_right.get_item("j_joker").unwrap().unlocked);
+    assert!(prefer_right.get_item("j_mod_thing").is_none());
+}
@@ -0,0 +1,83 @@
+use bal_save::enums::{ExportFormat, ItemCategory};
+use bal_save::registry::{Registry, use_bundled};
+use bal_save::saves::{ItemKey, Meta};
+
+#[test]
+fn keys_are_split_into_type_mod_and_name() {
+    assert!(use_bundled());
+    let registry = Registry::bundled();
+    let modded = ItemKey::parse("j_cry_happy_house", &registry);
+    assert_eq!(modded.type_prefix, "j");
+    assert_eq!(modded.mod_prefix, Some("cry"));
+    assert_eq!(modded.name, "happy_house");
+    assert_eq!(modded.category(), Some(ItemCategory::Joker));
+
+    // Vanilla keys never have a mod prefix
+    let vanilla = ItemKey::parse("j_gros_michel", &registry);
+    assert_eq!(vanilla.mod_prefix, None);
+    assert_eq!(vanilla.name, "gros_michel");
+    assert_eq!(
+        ItemKey::parse("bl_ox", &registry).category(),
+        Some(ItemCategory::Blind)
+    );
+}
+
+#[test]
+fn bulk_edits_work_per_mod() {
+    assert!(use_bundled());
+    let csv = "name,alerted,discovered,unlocked\n\
+        j_cry_happyhouse,false,false,false\n\
+        c_cry_gateway,false,false,false\n\
+        j_mp_defensive_joker,false,true,false\n";
+    let mut meta = Meta::import(csv, ExportFormat::Csv, true).unwrap();
+    assert_eq!(meta.mods(), vec!["cry", "mp"]);
+    assert_eq!(meta.item_mod("j_cry_happyhouse"), Some("cry"));
+    assert_eq!(meta.item_mod("j_blueprint"), None);
+
+    meta.record(|meta| meta.unlock_mod("cry", Some(ItemCategory::Joker)));
+    assert!(meta.get_item("j_cry_happyhouse").unwrap().unlocked);
+    assert!(!meta.get_item("c_cry_gateway").unwrap().unlocked);
+    assert!(!meta.get_item("j_blueprint").unwrap().unlocked);
+
+    // Removing a mod drops its keys, as one undo step
+    assert_eq!(meta.record(|meta| meta.remove_mod("cry")), 2);
+    assert_eq!(meta.mods(), vec!["mp"]);
+    assert!(meta.get_item("c_cry_gateway").is_none());
+    meta.undo();
+    assert_eq!(
+        meta.mod_items("cry"),
+        vec!["c_cry_gateway", "j_cry_happyhouse"]
+    );
+
+    let data = meta.to_lua_data().unwrap();
+    let reloaded = Meta::from_lua_table(data).unwrap();
+    assert_eq!(reloaded.item_mod("j_mp_defensive_joker"), Some("mp"));
+}
+
+#[test]
+fn unknown_prefixes_are_not_mods() {
+    assert!(use_bundled());
+    let registry = Registry::bundled();
+    let key = ItemKey::parse("j_foo_bar", &registry);
+    assert_eq!(key.mod_prefix, None);
+    assert_eq!(key.name, "foo_bar");
+    assert!(!key.known);
+    assert!(ItemKey::parse("j_cry_anything", &registry).known);
+    assert!(ItemKey::parse("j_blueprint", &registry).known);
+
+    // A registry with items from a mod knows its prefix
+    let modded = Registry::from_toml(
+        r#"items = [{ key = "j_foo_baz", category = "joker", mod = "foo" }]"#,
+    )
+    .unwrap();
+    assert_eq!(ItemKey::parse("j_foo_bar", &modded).mod_prefix, Some("foo"));
+
+    let csv = "name,alerted,discovered,unlocked\n\
+        j_foo_bar,false,true,false\n\
+        j_cry_happyhouse,false,false,false\n";
+    let mut meta = Meta::import(csv, ExportFormat::Csv, true).unwrap();
+    assert_eq!(meta.mods(), vec!["cry"]);
+    assert_eq!(meta.remove_mod("foo"), 0);
+    assert_eq!(meta.remove_mod("cry"), 1);
+    assert!(meta.get_item("j_foo_bar").is_some());
+}
@@ -0,0 +1,189 @@
+use bal_save::enums::SaveError;
+use bal_save::lua::{self, ParseErrorKind, SaveKey, SaveValue};
+
+/// Parses a table with a single value under "v" and returns the value
+fn parse_value(text: &str) -> SaveValue {
+    let table = lua::parse(&format!("return {{[\"v\"]={}}}", text)).unwrap();
+    table.as_table().unwrap().get("v").unwrap().clone()
+}
+
+#[test]
+fn errors_have_a_line_and_column() {
+    let err = lua::parse("return {\n  [\"a\"]=\"x\n\"}").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::Syntax);
+    assert_eq!((err.line, err.column), (2, 9));
+    assert_eq!(
+        err.to_string(),
+        "Parse error at line 2, column 9: Unfinished string"
+    );
+
+    let err = lua::parse("return {\n\n[\"a\"]=1 [\"b\"]=2}").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 9));
+
+    let err = lua::parse("return {[\"a\"]=\"\\q\"}").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 16));
+}
+
+#[test]
+fn escapes_are_decoded() {
+    assert_eq!(
+        parse_value(r#""\65\066\0672\x41\x6a""#),
+        SaveValue::String("ABC2Aj".to_string())
+    );
+    assert_eq!(
+        parse_value(r#""\u{48}\u{20AC}\u{1F0CF}""#),
+        SaveValue::String("H€🃏".to_string())
+    );
+    assert_eq!(
+        parse_value("\"a\\z   \n   b\\\nc\""),
+        SaveValue::String("ab\nc".to_string())
+    );
+    assert_eq!(
+        parse_value(r#"'\a\b\f\n\r\t\v\\\"\''"#),
+        SaveValue::String("\x07\x08\x0c\n\r\t\x0b\\\"'".to_string())
+    );
+
+    for bad in [r#""\256""#, r#""\xg0""#, r#""\u{110000}""#, r#""\q""#] {
+        let err = lua::parse(bad).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Syntax, "{}", bad);
+    }
+}
+
+#[test]
+fn long_strings_and_brackets() {
+    assert_eq!(
+        parse_value("[[\nfirst\nsecond]]"),
+        SaveValue::String("first\nsecond".to_string())
+    );
+    assert_eq!(
+        parse_value("[==[a]]b]=]c]==]"),
+        SaveValue::String("a]]b]=]c".to_string())
+    );
+    assert_eq!(
+        parse_value(r"[[no \n escapes]]"),
+        SaveValue::String(r"no \n escapes".to_string())
+    );
+
+    // A long string can be a key when there is a space after the bracket
+    let table = lua::parse("return {[ [[k]] ]=1}").unwrap();
+    assert_eq!(
+        table.as_table().unwrap().get("k"),
+        Some(&SaveValue::Integer(1))
+    );
+
+    let err = lua::parse("return [==[never closed]=]").unwrap_err();
+    assert_eq!(err.message, "Unfinished long string");
+}
+
+#[test]
+fn comments_are_skipped() {
+    let text = "-- written by hand\n\
+        return { --[[ block ]] a = 1, -- trailing\n\
+        b = 2 --[==[ a ]] in ]==] , }";
+    let value = lua::parse(text).unwrap();
+    let table = value.as_table().unwrap();
+    assert_eq!(table.get("a"), Some(&SaveValue::Integer(1)));
+    assert_eq!(table.get("b"), Some(&SaveValue::Integer(2)));
+    assert_eq!(table.len(), 2);
+
+    let err = lua::parse("return {} --[[ never closed").unwrap_err();
+    assert_eq!(err.message, "Unfinished comment");
+}
+
+#[test]
+fn positional_values_are_numbered_from_one() {
+    let value = lua::parse(r#"return {"a", [5]="e", "b"; x=1, "c", nil, "d"}"#)
+        .unwrap();
+    let table = value.as_table().unwrap();
+    let keys: Vec<&SaveKey> = table.iter().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        vec![
+            &SaveKey::Int(1),
+            &SaveKey::Int(5),
+            &SaveKey::Int(2),
+            &SaveKey::from("x"),
+            &SaveKey::Int(3),
+        ]
+    );
+    // nil takes a position without being stored, so "d" replaces [5]
+    assert_eq!(table.get(SaveKey::Int(4)), None);
+    assert_eq!(
+        table.get(SaveKey::Int(5)),
+        Some(&SaveValue::String("d".to_string()))
+    );
+
+    // Float keys with no fraction are integers, like in lua
+    let value = lua::parse("return {[2.0]=true}").unwrap();
+    assert!(value.as_table().unwrap().contains_key(SaveKey::Int(2)));
+}
+
+#[test]
+fn numbers_are_integers_or_floats() {
+    assert_eq!(parse_value("0x1F"), SaveValue::Integer(31));
+    assert_eq!(parse_value("-0xff"), SaveValue::Integer(-255));
+    assert_eq!(parse_value("0x7fffffffffffffff"), SaveValue::Integer(i64::MAX));
+    // Hex integers wrap around, decimal ones become floats
+    assert_eq!(parse_value("0xffffffffffffffff"), SaveValue::Integer(-1));
+    assert_eq!(parse_value("0x10000000000000001"), SaveValue::Integer(1));
+    assert_eq!(
+        parse_value("9223372036854775807"),
+        SaveValue::Integer(i64::MAX)
+    );
+    assert_eq!(
+        parse_value("9223372036854775808"),
+        SaveValue::Float(9223372036854775808.0)
+    );
+    assert_eq!(
+        parse_value("-9223372036854775808"),
+        SaveValue::Float(-9223372036854775808.0)
+    );
+
+    assert_eq!(parse_value("1e3"), SaveValue::Float(1000.0));
+    assert_eq!(parse_value(".5"), SaveValue::Float(0.5));
+    assert_eq!(parse_value("- 2.5E-1"), SaveValue::Float(-0.25));
+
+    for bad in ["1.2.3", "0x", "12abc", "1e"] {
+        let err = lua::parse(&format!("return {{{}}}", bad)).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Syntax, "{}", bad);
+    }
+}
+
+#[test]
+fn code_is_rejected() {
+    let cases = [
+        r#"return os.execute("x")"#,
+        "return {a=f()}",
+        "return {[1]=x}",
+        r#"return load("return 1")()"#,
+        "return {a=1+2}",
+        "return {a=-inf}",
+        "return {a=-nan}",
+        "return {a=- infinity}",
+        "return {a=-x}",
+        "return {a=- -1}",
+    ];
+    for text in cases {
+        let err = lua::parse(text).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Code, "{}", text);
+    }
+}
+
+#[test]
+fn limits_are_enforced() {
+    let depth = lua::MAX_DEPTH;
+    let nested = format!("return {}{}", "{".repeat(depth), "}".repeat(depth));
+    assert!(lua::parse(&nested).is_ok());
+    let nested =
+        format!("return {}{}", "{".repeat(depth + 1), "}".repeat(depth + 1));
+    assert_eq!(lua::parse(&nested).unwrap_err().kind, ParseErrorKind::Limit);
+
+    // A small file that inflates past the limit is a bomb, not a save
+    let padding = " ".repeat(lua::MAX_INFLATED_SIZE as usize);
+    let bomb = lua::deflate(&format!("return {{}}{}", padding)).unwrap();
+    assert!(bomb.len() < 1024 * 1024);
+    match lua::parse_save_data(&bomb).unwrap_err() {
+        SaveError::Parse(err) => assert_eq!(err.kind, ParseErrorKind::Limit),
+        err => panic!("expected a limit error, got {:?}", err),
+    }
+}
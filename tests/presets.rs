@@ -0,0 +1,107 @@
+use bal_save::enums::ExportFormat;
+use bal_save::presets::{self, Preset, PresetBase, PresetRule};
+use bal_save::registry::use_bundled;
+use bal_save::saves::Meta;
+
+#[test]
+fn rules_apply_in_order_after_the_base() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_defaults();
+    meta.unlock_all_type("v_");
+
+    let preset = Preset::from_rules(
+        "Tournament",
+        PresetBase::Current,
+        vec![
+            PresetRule {
+                prefix: Some("j_".to_string()),
+                unlocked: Some(true),
+                ..PresetRule::default()
+            },
+            PresetRule {
+                key: Some("j_blueprint".to_string()),
+                unlocked: Some(false),
+                ..PresetRule::default()
+            },
+        ],
+    );
+
+    // Working out the changes leaves the meta as it is
+    let changes = preset.changes(&meta);
+    assert!(!meta.get_item("j_brainstorm").unwrap().unlocked);
+    assert!(changes.iter().any(|change| change.name == "j_brainstorm"));
+    assert!(changes.iter().all(|change| change.name.starts_with("j_")));
+
+    preset.apply(&mut meta);
+    assert!(meta.get_item("j_brainstorm").unwrap().unlocked);
+    assert!(!meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(meta.get_item("v_grabber").unwrap().discovered);
+    assert!(preset.changes(&meta).is_empty());
+
+    // The built in presets start from their base
+    let fresh = &Preset::builtin()[0];
+    fresh.apply(&mut meta);
+    assert!(meta.diff(&Meta::from_defaults()).is_empty());
+}
+
+#[test]
+fn captured_presets_are_saved_and_listed() {
+    assert!(use_bundled());
+    let dir = std::env::temp_dir().join("bal-save-presets-test");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut meta = Meta::from_defaults();
+    meta.unlock_all_type("j_");
+    let preset = Preset::capture("All Jokers / Run 2", &meta);
+    let path = preset.save(&dir).unwrap();
+    assert_eq!(path.file_name().unwrap(), "All_Jokers___Run_2.toml");
+    Preset::builtin()[1].save(&dir).unwrap();
+
+    let listed = presets::list_presets(&dir);
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0], preset);
+
+    let mut fresh = Meta::from_defaults();
+    listed[0].apply(&mut fresh);
+    assert!(fresh.diff(&meta).is_empty());
+
+    listed[0].delete(&dir).unwrap();
+    assert_eq!(presets::list_presets(&dir), vec![Preset::builtin()[1].clone()]);
+}
+
+#[test]
+fn builtin_presets_leave_unknown_items_alone() {
+    assert!(use_bundled());
+    let csv = "name,alerted,discovered,unlocked\n\
+        j_cry_happyhouse,false,false,false\n";
+    let mut meta = Meta::import(csv, ExportFormat::Csv, true).unwrap();
+    let builtin = Preset::builtin();
+    let preset = |name: &str| builtin.iter().find(|p| p.name == name).unwrap();
+
+    preset("Vanilla 100%").apply(&mut meta);
+    assert!(meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(!meta.get_item("bl_ox").unwrap().unlocked);
+    assert!(!meta.get_item("j_cry_happyhouse").unwrap().unlocked);
+
+    preset("Tournament Ruleset").apply(&mut meta);
+    assert!(meta.get_item("j_blueprint").unwrap().unlocked);
+    assert!(meta.get_item("b_blue").unwrap().unlocked);
+    assert!(!meta.get_item("j_blueprint").unwrap().alerted);
+    assert!(!meta.get_item("bl_ox").unwrap().discovered);
+    let modded = meta.get_item("j_cry_happyhouse").unwrap();
+    assert!(!modded.discovered && !modded.unlocked);
+}
+
+#[test]
+fn names_saved_as_the_same_file_are_rejected() {
+    assert!(use_bundled());
+    let dir = std::env::temp_dir().join("bal-save-presets-collide-test");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let meta = Meta::from_defaults();
+    Preset::capture("A b", &meta).save(&dir).unwrap();
+    // Saving a preset again under its own name replaces it
+    Preset::capture("A b", &meta).save(&dir).unwrap();
+    assert!(Preset::capture("A_b", &meta).save(&dir).is_err());
+    assert_eq!(presets::list_presets(&dir)[0].name, "A b");
+}
@@ -0,0 +1,93 @@
+use bal_save::lua;
+use bal_save::saves::Profile;
+
+const PROFILE: &str = include_str!("fixtures/profile.txt");
+
+#[test]
+fn unedited_profile_saves_unchanged() {
+    let data = lua::deflate(PROFILE).unwrap();
+    let profile = Profile::from_lua_table(data).unwrap();
+    assert_eq!(profile.career_stats.wins, 3);
+    assert_eq!(profile.joker_usage["j_joker"].wins[0], 2);
+    assert_eq!(profile.memory.deck, "Red Deck");
+
+    let saved = profile.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), PROFILE);
+}
+
+#[test]
+fn edited_profile_keeps_everything_else() {
+    let data = lua::deflate(PROFILE).unwrap();
+    let mut profile = Profile::from_lua_table(data).unwrap();
+    profile.career_stats.wins = 4;
+    profile.joker_usage.get_mut("j_joker").unwrap().wins[2] = 1;
+    profile.challenge_progress.completed.insert("c_omelette_1".to_string());
+
+    let saved = profile.to_lua_data().unwrap();
+    let expected = PROFILE
+        .replace("[\"c_wins\"]=3,", "[\"c_wins\"]=4,")
+        .replace("[2]=1,},[\"losses\"]", "[2]=1,[3]=1,},[\"losses\"]")
+        .replace(
+            "[\"completed\"]={}",
+            "[\"completed\"]={[\"c_omelette_1\"]=true,}",
+        );
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+}
+
+#[test]
+fn unusual_values_are_kept_until_edited() {
+    let text = "return {[\"career_stats\"]={},\
+        [\"deck_stakes\"]={[\"b_red\"]=\"high\",[\"b_blue\"]=300,[\"b_green\"]=2,},}";
+    let mut profile =
+        Profile::from_lua_table(lua::deflate(text).unwrap()).unwrap();
+    assert_eq!(profile.deck_stakes["b_red"], 0);
+    assert_eq!(profile.deck_stakes["b_blue"], u8::MAX);
+
+    // Nothing is added or rewritten, not even an empty MEMORY
+    let saved = profile.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), text);
+
+    profile.deck_stakes.insert("b_green".to_string(), 3);
+    profile.memory.stake = 2;
+    let saved = profile.to_lua_data().unwrap();
+    let expected = text.replace("=2,}", "=3,}").replace(
+        ",},}",
+        ",},[\"MEMORY\"]={[\"deck\"]=\"Red Deck\",[\"stake\"]=2,},}",
+    );
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+}
+
+#[test]
+fn counts_that_are_not_whole_are_kept_until_edited() {
+    let text = "return {[\"career_stats\"]={[\"c_wins\"]=2.5,[\"c_losses\"]=-1,},\
+        [\"joker_usage\"]={[\"j_joker\"]={[\"count\"]=1.5,[\"order\"]=-3,},},\
+        [\"stake\"]=-2,}";
+    let mut profile =
+        Profile::from_lua_table(lua::deflate(text).unwrap()).unwrap();
+    assert_eq!(profile.career_stats.wins, 2);
+    assert_eq!(profile.career_stats.losses, 0);
+    assert_eq!(profile.joker_usage["j_joker"].count, 1);
+
+    let saved = profile.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), text);
+
+    profile.career_stats.wins = 4;
+    let saved = profile.to_lua_data().unwrap();
+    let expected = text.replace("=2.5", "=4");
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+}
+
+#[test]
+fn missing_strings_are_not_added() {
+    let text = "return {[\"career_stats\"]={},\
+        [\"hand_usage\"]={[\"Flush\"]={[\"count\"]=2,},},\
+        [\"high_scores\"]={[\"hand\"]={[\"amt\"]=5,},},\
+        [\"MEMORY\"]={[\"stake\"]=1,},}";
+    let profile = Profile::from_lua_table(lua::deflate(text).unwrap()).unwrap();
+    assert_eq!(profile.memory.deck, "");
+    assert_eq!(profile.hand_usage["Flush"].order, "");
+    assert_eq!(profile.high_scores["hand"].label, "");
+
+    let saved = profile.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), text);
+}
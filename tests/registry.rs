@@ -0,0 +1,171 @@
+use bal_save::enums::{ItemCategory, MetaFlag, SaveType};
+use bal_save::lua;
+use bal_save::registry::{Registries, Registry, use_bundled};
+use bal_save::saves::Meta;
+
+#[test]
+fn bundled_items_match_the_game() {
+    assert!(use_bundled());
+    let registry = Registry::bundled();
+    assert_eq!(registry.len(), 342);
+    let new_profile = registry.entries().filter(|e| e.in_new_profile());
+    assert_eq!(new_profile.count(), 183);
+
+    let ox = registry.get("bl_ox").unwrap();
+    assert_eq!(ox.category, ItemCategory::Blind);
+    assert!(!ox.can_have(MetaFlag::Unlocked));
+    let joker = registry.get("j_joker").unwrap();
+    assert_eq!(joker.default_flag(MetaFlag::Unlocked), Some(true));
+    assert!(registry.entries().all(|e| e.mod_prefix.is_none()));
+}
+
+#[test]
+fn every_bundled_file_parses() {
+    assert!(use_bundled());
+    let assets = concat!(env!("CARGO_MANIFEST_DIR"), "/src/assets");
+    let mut parsed = 0;
+    for entry in std::fs::read_dir(assets).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            let text = std::fs::read_to_string(&path).unwrap();
+            if let Err(err) = Registry::from_toml(&text) {
+                panic!("{} does not parse: {}", path.display(), err);
+            }
+            parsed += 1;
+        }
+    }
+    assert!(parsed > 0);
+}
+
+#[test]
+fn user_files_are_read_over_the_bundled_items() {
+    assert!(use_bundled());
+    let dir = std::env::temp_dir().join("bal-save-registry-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("cryptid.toml"),
+        r#"items = [
+            { key = "j_cry_happyhouse", category = "joker", mod = "cry", discovered = false, unlocked = false },
+            { key = "j_blueprint", category = "joker", alerted = false, discovered = false, unlocked = true },
+        ]"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("broken.toml"), "items = [{ key = 1 }]").unwrap();
+
+    let mut registries = Registries::bundled();
+    let errors = registries.extend_from_dir(&dir);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, dir.join("broken.toml"));
+    let registry = registries.base();
+    assert_eq!(registry.len(), 343);
+
+    let modded = registry.get("j_cry_happyhouse").unwrap();
+    assert_eq!(modded.mod_prefix.as_deref(), Some("cry"));
+    assert!(!modded.can_have(MetaFlag::Alerted));
+    assert!(!modded.in_new_profile());
+    let item = modded.default_item();
+    assert_eq!(item.mod_prefix(), Some("cry"));
+    assert!(item.can_be_discovered() && !item.discovered);
+
+    let blueprint = registry.get("j_blueprint").unwrap();
+    assert_eq!(blueprint.default_flag(MetaFlag::Unlocked), Some(true));
+    assert!(registry.knows_mod("cry") && !registry.knows_mod("foo"));
+}
+
+#[test]
+fn registries_are_keyed_by_game_version() {
+    assert!(use_bundled());
+    let dir = std::env::temp_dir().join("bal-save-registry-version-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("1.1.toml"),
+        r#"version = "1.1"
+        items = [
+            { key = "j_new", category = "joker", alerted = false, discovered = false, unlocked = true },
+        ]"#,
+    )
+    .unwrap();
+
+    let mut registries = Registries::bundled();
+    assert!(registries.extend_from_dir(&dir).is_empty());
+    assert_eq!(registries.versions().collect::<Vec<_>>(), ["1.0.1", "1.1"]);
+
+    assert!(registries.knows("1.0.1o-FULL"));
+    assert!(!registries.knows("1.0.10"));
+    let newer = registries.for_version(Some("1.1.2-FULL"));
+    assert_eq!(newer.version(), Some("1.1"));
+    assert_eq!(newer.len(), 343);
+    assert!(newer.contains("j_blueprint"));
+
+    // Unknown versions fall back to the bundled items
+    let unknown = registries.for_version(Some("0.9.8"));
+    assert_eq!(unknown.version(), Some("1.0.1"));
+    assert!(!unknown.contains("j_new"));
+}
+
+#[test]
+fn meta_files_take_the_version_of_the_run_next_to_them() {
+    assert!(use_bundled());
+    let dir = std::env::temp_dir().join("bal-save-registry-meta-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let meta_path = dir.join("meta.jkr");
+    let meta_text = include_str!("fixtures/meta.txt");
+    std::fs::write(&meta_path, lua::deflate(meta_text).unwrap()).unwrap();
+
+    // Without a run next to it the version is not known, which is no warning
+    let mut save = SaveType::from_file(&meta_path).unwrap();
+    save.find_version(&meta_path);
+    assert!(save.summary().contains("Version: unknown\n"));
+    let SaveType::Meta(meta) = save else {
+        panic!("not a meta file");
+    };
+    assert_eq!(meta.version(), None);
+    assert!(meta.knows_version() && meta.version_warning().is_none());
+
+    // The run is only looked at when asked to
+    let save_text = include_str!("fixtures/save.txt");
+    std::fs::write(dir.join("save.jkr"), lua::deflate(save_text).unwrap())
+        .unwrap();
+    let mut save = SaveType::from_file(&meta_path).unwrap();
+    assert!(save.summary().contains("Version: unknown\n"));
+    save.find_version(&meta_path);
+    assert!(save.summary().contains("Version: 1.0.1o-FULL\n"));
+    let SaveType::Meta(mut meta) = save else {
+        panic!("not a meta file");
+    };
+    assert_eq!(meta.version(), Some("1.0.1o-FULL"));
+    assert!(meta.knows_version());
+
+    // A version found that way is not written to the meta file
+    let written = meta.to_lua_data().unwrap();
+    assert!(!lua::inflate(&written).unwrap().contains("VERSION"));
+
+    meta.set_version(Some("2.0.0".to_string()));
+    assert!(!meta.knows_version());
+    assert_eq!(
+        meta.version_warning().unwrap(),
+        "there is no item data for version 2.0.0, the items of 1.0.1 are used"
+    );
+}
+
+#[test]
+fn meta_files_keep_their_own_version() {
+    assert!(use_bundled());
+    let text = include_str!("fixtures/meta.txt").replacen(
+        "return {",
+        "return {[\"VERSION\"]=\"1.0.1o-FULL\",",
+        1,
+    );
+    let meta = Meta::from_lua_table(lua::deflate(&text).unwrap()).unwrap();
+    assert_eq!(meta.version(), Some("1.0.1o-FULL"));
+
+    let reloaded = Meta::from_lua_table(meta.to_lua_data().unwrap()).unwrap();
+    assert_eq!(reloaded.version(), Some("1.0.1o-FULL"));
+
+    let mut version = None;
+    meta.preview(|copy| version = copy.version().map(str::to_string));
+    assert_eq!(version.as_deref(), Some("1.0.1o-FULL"));
+}
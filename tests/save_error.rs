@@ -0,0 +1,33 @@
+use bal_save::enums::{SaveError, SaveKind};
+use bal_save::lua;
+use bal_save::registry::use_bundled;
+use bal_save::saves::{Meta, Profile};
+
+#[test]
+fn errors_can_be_matched_on() {
+    assert!(use_bundled());
+    let err = Meta::from_lua_table(b"not a save file".to_vec()).err().unwrap();
+    assert!(matches!(err, SaveError::Decompress(_)));
+
+    let data = lua::deflate("return {[\"a\"]=1,\n[\"b\"]=}").unwrap();
+    match Meta::from_lua_table(data).err().unwrap() {
+        SaveError::Parse(err) => assert_eq!((err.line, err.column), (2, 7)),
+        err => panic!("expected a parse error, got {:?}", err),
+    }
+
+    let profile = include_str!("fixtures/profile.txt");
+    let data = lua::deflate(profile).unwrap();
+    assert!(matches!(
+        Meta::from_lua_table(data).err().unwrap(),
+        SaveError::WrongFileType {
+            expected: SaveKind::Meta,
+            found: SaveKind::Profile
+        }
+    ));
+
+    let data = lua::deflate("return {[\"stake\"]=1,}").unwrap();
+    match Profile::from_lua_table(data).err().unwrap() {
+        SaveError::MissingSubtable(name) => assert_eq!(name, "career_stats"),
+        err => panic!("expected a missing subtable, got {:?}", err),
+    }
+}
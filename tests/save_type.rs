@@ -0,0 +1,29 @@
+use bal_save::enums::{SaveKind, SaveType};
+use bal_save::lua;
+use bal_save::registry::use_bundled;
+
+fn open(text: &str) -> SaveType {
+    SaveType::from_lua_data(lua::deflate(text).unwrap()).unwrap()
+}
+
+#[test]
+fn files_are_detected_from_their_keys() {
+    assert!(use_bundled());
+    let fixtures = [
+        (include_str!("fixtures/meta.txt"), SaveKind::Meta),
+        (include_str!("fixtures/profile.txt"), SaveKind::Profile),
+        (include_str!("fixtures/save.txt"), SaveKind::Save),
+        (include_str!("fixtures/settings.txt"), SaveKind::Settings),
+        ("return {[\"other\"]={[1]=true,},}", SaveKind::Unknown),
+    ];
+    for (text, kind) in fixtures {
+        let save = open(text);
+        assert_eq!(save.kind(), kind);
+        // Meta files are rewritten with every known item, so only the other
+        // kinds come back out unchanged
+        if kind == SaveKind::Meta {
+            continue;
+        }
+        assert_eq!(lua::inflate(&save.to_lua_data().unwrap()).unwrap(), text);
+    }
+}
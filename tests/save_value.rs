@@ -0,0 +1,101 @@
+use bal_save::lua::{self, PathError, SaveKey, SaveTable, SaveValue};
+
+const SAVE: &str = include_str!("fixtures/save.txt");
+
+#[test]
+fn paths_reach_nested_values() {
+    let save = lua::parse(SAVE).unwrap();
+    assert_eq!(save.get_path("GAME.dollars"), Some(&SaveValue::Integer(-3)));
+    assert_eq!(
+        save.get_path("cardAreas.jokers.cards[1].ability.extra"),
+        Some(&SaveValue::String("MANUAL_REPLACE".to_string()))
+    );
+    assert_eq!(
+        save.get_path("[\"BACK\"].key"),
+        Some(&SaveValue::String("b_red".to_string()))
+    );
+    assert_eq!(save.get_path("GAME.missing"), None);
+    assert_eq!(save.get_path("GAME..dollars"), None);
+}
+
+#[test]
+fn set_path_edits_in_place_and_round_trips() {
+    let mut save = lua::parse(SAVE).unwrap();
+    let old = save.set_path("GAME.dollars", SaveValue::Integer(25)).unwrap();
+    assert_eq!(old, Some(SaveValue::Integer(-3)));
+    save.set_path("GAME.new.nested", SaveValue::Bool(true)).unwrap();
+    assert_eq!(
+        save.set_path("VERSION.minor", SaveValue::Integer(1)),
+        Err(PathError::NotATable("VERSION".to_string()))
+    );
+
+    let data = save.to_lua_data().unwrap();
+    let reloaded = SaveValue::from_lua_data(&data).unwrap();
+    assert_eq!(reloaded, save);
+    assert_eq!(
+        reloaded.get_path("GAME.new.nested"),
+        Some(&SaveValue::Bool(true))
+    );
+    // Editing a value keeps its place, so the rest of the file is unchanged
+    assert_eq!(
+        lua::str_pack(&reloaded),
+        SAVE.replace("[\"dollars\"]=-3,", "[\"dollars\"]=25,").replace(
+            "[\"blind_ante\"]=2,},},",
+            "[\"blind_ante\"]=2,},[\"new\"]={[\"nested\"]=true,},},"
+        )
+    );
+    assert_eq!(
+        lua::parse_path("cardAreas.jokers.cards[1]").unwrap(),
+        vec![
+            SaveKey::from("cardAreas"),
+            SaveKey::from("jokers"),
+            SaveKey::from("cards"),
+            SaveKey::Int(1)
+        ]
+    );
+}
+
+#[test]
+fn paths_with_any_key_round_trip() {
+    let keys = vec![
+        SaveKey::from("GAME"),
+        SaveKey::from("a]b"),
+        SaveKey::from("say \"hi\""),
+        SaveKey::from("back\\slash\\"),
+        SaveKey::from("\"]"),
+        SaveKey::from(""),
+        SaveKey::Int(-1),
+        SaveKey::from("dots.in.it"),
+    ];
+    let path = lua::format_path(&keys);
+    assert_eq!(lua::parse_path(&path).unwrap(), keys);
+    for key in &keys {
+        let one = std::slice::from_ref(key);
+        assert_eq!(lua::parse_path(&lua::format_path(one)).unwrap(), one);
+    }
+
+    assert_eq!(
+        lua::parse_path(r#"a[ "x\"]" ].b"#).unwrap(),
+        vec![SaveKey::from("a"), SaveKey::from("x\"]"), SaveKey::from("b")]
+    );
+    assert!(lua::parse_path(r#"a["x"#).is_err());
+    assert!(lua::parse_path(r#"a["x\"]"#).is_err());
+    assert!(lua::parse_path(r#"a["x"b]"#).is_err());
+}
+
+#[test]
+fn tables_find_keys_after_removing_one() {
+    let mut table = SaveTable::new();
+    for i in 0..5 {
+        table.insert(SaveKey::Int(i), SaveValue::Integer(i * 10));
+    }
+    assert_eq!(table.remove(SaveKey::Int(1)), Some(SaveValue::Integer(10)));
+    assert_eq!(table.get(SaveKey::Int(3)), Some(&SaveValue::Integer(30)));
+    table.insert(SaveKey::Int(1), SaveValue::Integer(11));
+    table.insert(SaveKey::Int(4), SaveValue::Integer(41));
+    let values: Vec<&SaveValue> = table.iter().map(|(_, v)| v).collect();
+    assert_eq!(
+        values,
+        [0, 20, 30, 41, 11].map(SaveValue::Integer).iter().collect::<Vec<_>>()
+    );
+}
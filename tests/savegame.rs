@@ -0,0 +1,56 @@
+use bal_save::lua;
+use bal_save::saves::SaveGame;
+
+const SAVE: &str = include_str!("fixtures/save.txt");
+
+#[test]
+fn unedited_save_saves_unchanged() {
+    let data = lua::deflate(SAVE).unwrap();
+    let save = SaveGame::from_lua_table(data).unwrap();
+    assert_eq!(save.version, "1.0.1o-FULL");
+    assert_eq!(save.seed, "7LB2WVPK");
+    assert_eq!(save.deck, "Red Deck");
+    assert_eq!(save.dollars, -3.0);
+    assert_eq!(save.counts[0], 2);
+
+    let saved = save.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), SAVE);
+}
+
+#[test]
+fn edited_save_keeps_everything_else() {
+    let data = lua::deflate(SAVE).unwrap();
+    let mut save = SaveGame::from_lua_table(data).unwrap();
+    save.dollars = 100.0;
+    save.counts[0] = 3;
+
+    let saved = save.to_lua_data().unwrap();
+    let expected =
+        SAVE.replace("[\"dollars\"]=-3,", "[\"dollars\"]=100,").replace(
+            "[\"ante\"]=2,[\"blind_ante\"]=2,",
+            "[\"ante\"]=3,[\"blind_ante\"]=3,",
+        );
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+    assert!(expected.contains("MANUAL_REPLACE"));
+}
+
+#[test]
+fn counts_that_are_not_whole_are_kept_until_edited() {
+    let text = SAVE.replace("[\"ante\"]=2,", "[\"ante\"]=2.5,");
+    let data = lua::deflate(&text).unwrap();
+    let mut save = SaveGame::from_lua_table(data).unwrap();
+    assert_eq!(save.counts[0], 2);
+
+    save.dollars = 100.0;
+    let saved = save.to_lua_data().unwrap();
+    let expected = text.replace("[\"dollars\"]=-3,", "[\"dollars\"]=100,");
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+
+    save.counts[0] = 3;
+    let saved = save.to_lua_data().unwrap();
+    let expected = expected.replace(
+        "[\"ante\"]=2.5,[\"blind_ante\"]=2,",
+        "[\"ante\"]=3,[\"blind_ante\"]=3,",
+    );
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+}
@@ -0,0 +1,41 @@
+use bal_save::lua;
+use bal_save::saves::Settings;
+
+const SETTINGS: &str = include_str!("fixtures/settings.txt");
+
+#[test]
+fn unedited_settings_save_unchanged() {
+    let data = lua::deflate(SETTINGS).unwrap();
+    let settings = Settings::from_lua_table(data).unwrap();
+    assert_eq!(settings.language, "en-us");
+    assert_eq!(settings.game_speed, 1.0);
+    assert_eq!(settings.volume, 50.0);
+    assert_eq!(settings.screenshake, 50.0);
+    assert!(!settings.high_contrast);
+
+    let saved = settings.to_lua_data().unwrap();
+    assert_eq!(lua::inflate(&saved).unwrap(), SETTINGS);
+}
+
+#[test]
+fn edited_settings_keep_everything_else() {
+    let data = lua::deflate(SETTINGS).unwrap();
+    let mut settings = Settings::from_lua_table(data).unwrap();
+    settings.language = "fr".to_string();
+    settings.game_speed = 4.0;
+    settings.music_volume = 0.0;
+    settings.screenshake = 0.0;
+    settings.high_contrast = true;
+
+    let saved = settings.to_lua_data().unwrap();
+    let expected = SETTINGS
+        .replace("[\"language\"]=\"en-us\"", "[\"language\"]=\"fr\"")
+        .replace("[\"GAMESPEED\"]=1,", "[\"GAMESPEED\"]=4,")
+        .replace("[\"music_volume\"]=100,", "[\"music_volume\"]=0,")
+        .replace("[\"screenshake\"]=true,", "[\"screenshake\"]=0,")
+        .replace(
+            "[\"colourblind_option\"]=false,",
+            "[\"colourblind_option\"]=true,",
+        );
+    assert_eq!(lua::inflate(&saved).unwrap(), expected);
+}
@@ -0,0 +1,31 @@
+use bal_save::enums::{ItemCategory, MetaFlag};
+use bal_save::registry::{registry, use_bundled};
+use bal_save::saves::{FlagCount, Meta};
+
+#[test]
+fn stats_count_against_the_registry() {
+    assert!(use_bundled());
+    let mut meta = Meta::from_defaults();
+    let jokers = registry().category(ItemCategory::Joker).count();
+
+    let before = meta.category_stats(ItemCategory::Joker);
+    meta.unlock_all_type("j_");
+    let after = meta.category_stats(ItemCategory::Joker);
+    assert_eq!(after.discovered, FlagCount { done: jokers, total: jokers });
+    assert_eq!(after.unlocked.fraction(), 1.0);
+    assert!(before.discovered.done < jokers);
+
+    // Blinds can not be unlocked, so they have nothing to count
+    let blinds = meta.category_stats(ItemCategory::Blind);
+    assert_eq!(blinds.unlocked.total, 0);
+    assert_eq!(blinds.unlocked.fraction(), 1.0);
+
+    let stats = meta.stats();
+    assert_eq!(stats.len(), ItemCategory::ALL.len());
+    let total: usize = stats.iter().map(|s| s.discovered.total).sum();
+    let discoverable = registry()
+        .entries()
+        .filter(|entry| entry.can_have(MetaFlag::Discovered))
+        .count();
+    assert_eq!(total, discoverable);
+}
@@ -0,0 +1,137 @@
+use bal_save::lua::{
+    self, LuaContext, SaveKey, SaveTable, SaveValue, format_number, str_pack,
+};
+
+/// Fixtures written by hand in the format the game's STR_PACK produces, see
+/// output_matches_the_games_str_pack for a check against STR_PACK itself
+const FIXTURES: [(&str, &str); 3] = [
+    ("meta", include_str!("fixtures/meta.txt")),
+    ("profile", include_str!("fixtures/profile.txt")),
+    ("save", include_str!("fixtures/save.txt")),
+];
+
+#[test]
+fn fixtures_round_trip_byte_for_byte() {
+    for (name, fixture) in FIXTURES {
+        let value = lua::parse(fixture).unwrap();
+        assert_eq!(str_pack(&value), fixture, "fixture {}", name);
+    }
+}
+
+#[test]
+fn fixtures_survive_deflate() {
+    for (name, fixture) in FIXTURES {
+        let value = lua::parse(fixture).unwrap();
+        let data = lua::to_save_data(&value).unwrap();
+        assert_eq!(lua::inflate(&data).unwrap(), fixture, "fixture {}", name);
+        assert_eq!(lua::parse_save_data(&data).unwrap(), value);
+    }
+}
+
+#[test]
+fn numbers_are_formatted_like_luajit() {
+    let cases = [
+        (0.0, "0"),
+        (4.0, "4"),
+        (-3.75, "-3.75"),
+        (0.1, "0.1"),
+        (1.0 / 3.0, "0.33333333333333"),
+        (100.0, "100"),
+        (0.0001, "0.0001"),
+        (0.00001, "1e-05"),
+        (1e15, "1e+15"),
+        (12345678901234.0, "12345678901234"),
+        (123456789012346.0, "1.2345678901235e+14"),
+        (1e100, "1e+100"),
+        (f64::INFINITY, "inf"),
+    ];
+    for (number, expected) in cases {
+        assert_eq!(format_number(number), expected);
+    }
+}
+
+#[test]
+fn strings_are_quoted_like_luajit() {
+    let cases = [
+        ("plain", r#""plain""#),
+        ("a\"b\\c", r#""a\"b\\c""#),
+        ("line\nbreak", "\"line\\\nbreak\""),
+        ("cr\rtab\t", r#""cr\13tab\9""#),
+        ("\u{1}2", r#""\0012""#),
+        ("nul\0", r#""nul\0""#),
+        ("del\u{7f}", r#""del\127""#),
+        ("Über", r#""Über""#),
+    ];
+    for (s, expected) in cases {
+        let mut table = SaveTable::new();
+        table.insert("s", SaveValue::String(s.to_string()));
+        let packed = str_pack(&SaveValue::Table(table));
+        assert_eq!(packed, format!(r#"return {{["s"]={},}}"#, expected));
+        assert_eq!(
+            lua::parse(&packed).unwrap().as_table().unwrap().get("s"),
+            Some(&SaveValue::String(s.to_string()))
+        );
+    }
+}
+
+#[test]
+fn keys_and_values_are_written_like_str_pack() {
+    let mut inner = SaveTable::new();
+    inner.insert(SaveKey::Int(1), SaveValue::Integer(-2));
+    inner.insert(SaveKey::Int(2), SaveValue::Float(2.5));
+    let mut table = SaveTable::new();
+    table.insert("list", SaveValue::Table(inner));
+    table.insert("empty", SaveValue::Table(SaveTable::new()));
+    table.insert("yes", SaveValue::Bool(true));
+    table.insert("no", SaveValue::Bool(false));
+
+    assert_eq!(
+        str_pack(&SaveValue::Table(table)),
+        r#"return {["list"]={[1]=-2,[2]=2.5,},["empty"]={},["yes"]=true,["no"]=false,}"#
+    );
+}
+
+/// The game's own STR_PACK, from functions/misc_functions.lua, without the
+/// branch for objects since plain tables never have one
+const GAME_STR_PACK: &str = r#"
+function STR_PACK(data, recursive)
+    local ret_str = (recursive and "" or "return ").."{"
+    for i, v in pairs(data) do
+        local type_i, type_v = type(i), type(v)
+        if type_i == "string" then
+            i = '['..string.format("%q", i)..']'
+        else
+            i = "["..i.."]"
+        end
+        if type_v == "table" then
+            v = STR_PACK(v, true)
+        else
+            if type_v == "string" then v = string.format("%q", v) end
+            if type_v == "boolean" then v = v and "true" or "false" end
+        end
+        ret_str = ret_str..i.."="..v..","
+    end
+    return ret_str.."}"
+end
+"#;
+
+#[test]
+fn output_matches_the_games_str_pack() {
+    let context = LuaContext::new();
+    context.lua.load(GAME_STR_PACK).exec().unwrap();
+    let game_str_pack: mlua::Function =
+        context.lua.globals().get("STR_PACK").unwrap();
+
+    // pairs has no set order for string keys, so each table has at most one
+    // and the rest of the keys are a sequence, which pairs walks in order
+    let text = r#"return {["career"]={[1]=-2,[2]=2.5,[3]=0.33333333333333,
+        [4]="a\"b\\c",[5]="line\
+break",[6]="cr\13tab\9",[7]="\0012",[8]="del\127",[9]="Über",
+        [10]=true,[11]=false,[12]={},[13]={["deep"]={[1]=1e+100,},},},}"#;
+    let value = lua::parse(text).unwrap();
+
+    let packed: String =
+        game_str_pack.call(context.save_value_to_lua(&value).unwrap()).unwrap();
+    assert_eq!(str_pack(&value), packed);
+    assert_eq!(lua::parse(&packed).unwrap(), value);
+}
@@ -0,0 +1,21 @@
+use bal_save::watcher::FileWatcher;
+
+#[test]
+fn changes_are_reported_once() {
+    let path = std::env::temp_dir().join("bal-save-watcher-test.jkr");
+    std::fs::write(&path, b"1").unwrap();
+    let mut watcher = FileWatcher::new(&path);
+    assert!(!watcher.changed());
+
+    std::fs::write(&path, b"22").unwrap();
+    assert!(watcher.changed());
+    assert!(!watcher.changed());
+
+    // The app's own writes are marked as seen
+    std::fs::write(&path, b"333").unwrap();
+    watcher.mark_seen();
+    assert!(!watcher.changed());
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(!watcher.changed());
+}